@@ -0,0 +1,127 @@
+// Known-correct answers, recorded per day in `data/answers.txt`. Each non-empty line that isn't a
+// comment has the form
+//
+//     <input file> <part> <answer>
+//
+// where the input file is relative to the `data` directory. Answers that span multiple lines (such
+// as rendered images) are written with `\n` in place of each newline.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub file: String,
+    pub part: u32,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    // The `data` directory that the input files are relative to.
+    pub data_dir: PathBuf,
+    pub answers: Vec<Answer>,
+}
+
+impl Answers {
+    // Load the manifest of the crate at `crate_dir`.
+    pub fn load(crate_dir: impl AsRef<Path>) -> Result<Answers, String> {
+        let data_dir = crate_dir.as_ref().join("data");
+        let path = data_dir.join("answers.txt");
+        let manifest = std::fs::read_to_string(&path)
+            .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+        let answers = parse_manifest(&manifest)
+            .map_err(|(line, e)| format!("{}:{}: {}", path.display(), line, e))?;
+        Ok(Answers { data_dir, answers })
+    }
+
+    // The input files that have recorded answers, in the order they first appear.
+    pub fn files(&self) -> Vec<&str> {
+        let mut files: Vec<&str> = Vec::new();
+        for a in &self.answers {
+            if !files.contains(&a.file.as_str()) {
+                files.push(&a.file);
+            }
+        }
+        files
+    }
+
+    pub fn expected(&self, file: &str, part: u32) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|a| a.file == file && a.part == part)
+            .map(|a| a.answer.as_str())
+    }
+}
+
+// Append an answer to the manifest of the crate at `crate_dir`, creating it if need be.
+pub fn record(crate_dir: impl AsRef<Path>, file: &str, part: u32, answer: &str) -> io::Result<()> {
+    let path = crate_dir.as_ref().join("data").join("answers.txt");
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    let mut manifest = OpenOptions::new().create(true).append(true).open(&path)?;
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        writeln!(manifest)?;
+    }
+    writeln!(
+        manifest,
+        "{} {} {}",
+        file,
+        part,
+        answer.replace('\n', "\\n")
+    )
+}
+
+// Parse the lines of a manifest. Errors are reported with their 1-based line number.
+fn parse_manifest(manifest: &str) -> Result<Vec<Answer>, (usize, String)> {
+    let mut answers = Vec::new();
+    for (i, line) in manifest.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.splitn(3, ' ');
+        let (file, part, answer) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(file), Some(part), Some(answer)) => (file, part, answer),
+            _ => return Err((i + 1, String::from("expected <file> <part> <answer>"))),
+        };
+        let part = match part.parse() {
+            Ok(part @ (1 | 2)) => part,
+            _ => return Err((i + 1, format!("invalid part {:?}", part))),
+        };
+        answers.push(Answer {
+            file: file.to_string(),
+            part,
+            answer: answer.replace("\\n", "\n"),
+        });
+    }
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let answers = parse_manifest(
+            "# comment\n\ninput.txt 1 42\ntest.txt 2 ##..\\n..##\ninput.txt 2 CMZ\n",
+        )
+        .unwrap();
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers[1].file, "test.txt");
+        assert_eq!(answers[1].part, 2);
+        assert_eq!(answers[1].answer, "##..\n..##");
+
+        let answers = Answers {
+            data_dir: PathBuf::from("data"),
+            answers,
+        };
+        assert_eq!(answers.files(), vec!["input.txt", "test.txt"]);
+        assert_eq!(answers.expected("input.txt", 2), Some("CMZ"));
+        assert_eq!(answers.expected("test.txt", 1), None);
+
+        assert_eq!(parse_manifest("input.txt 1").unwrap_err().0, 1);
+        assert_eq!(parse_manifest("\ninput.txt 3 42").unwrap_err().0, 2);
+    }
+}
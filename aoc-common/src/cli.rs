@@ -0,0 +1,329 @@
+// The command line shared by every day's binary:
+//
+//     dayNN [--test] [--format text|json] [--stream] [INPUT | -]
+//
+// Without arguments the puzzle input bundled with the crate (`data/input.txt`) is used, `--test`
+// selects the bundled sample (`data/test.txt`) instead, and `-` reads the input from stdin. Paths
+// to the bundled files are resolved against the crate directory, so the binaries work from any
+// working directory. The answers are printed as text, or as JSON (see `report`). The days that
+// implement `StreamSolver` accept `--stream`, to solve the input as it is read instead of reading
+// all of it first. Days with subcommands parse their arguments with `parse_command`, which
+// handles the input in the same way.
+
+use crate::report::{self, Format, Report};
+use crate::stream::StreamError;
+use crate::{Solver, StreamSolver};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    // The bundled puzzle input or sample in the `data` directory of the crate at `crate_dir`.
+    pub fn bundled(crate_dir: impl AsRef<Path>, test: bool) -> Source {
+        let name = if test { "test.txt" } else { "input.txt" };
+        Source::File(crate_dir.as_ref().join("data").join(name))
+    }
+
+    // Interpret a path given on the command line, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Source {
+        if arg == "-" {
+            Source::Stdin
+        } else {
+            Source::File(PathBuf::from(arg))
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Source::File(path) => std::fs::read_to_string(path),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    // Open the input for reading it incrementally.
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Source::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            Source::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+
+    // The name used to refer to the source in diagnostics.
+    pub fn name(&self) -> PathBuf {
+        match self {
+            Source::File(path) => path.clone(),
+            Source::Stdin => PathBuf::from("<stdin>"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub source: Source,
+    pub format: Format,
+    pub stream: bool,
+}
+
+// The arguments left to parse, for the options that take a value.
+pub struct Args<I: Iterator<Item = String>>(I);
+
+impl<I: Iterator<Item = String>> Args<I> {
+    // The value given to `option`, which is the next argument.
+    pub fn value(&mut self, option: &str) -> Result<String, String> {
+        self.0
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", option))
+    }
+}
+
+// Parse `[--test] [INPUT | -]` with other options in between, as the arguments of a subcommand.
+// `option` is called with each of the other options, and the arguments to take its value from,
+// and returns whether the option is one it knows.
+pub fn parse_command<I, F>(
+    args: I,
+    crate_dir: impl AsRef<Path>,
+    mut option: F,
+) -> Result<Source, String>
+where
+    I: Iterator<Item = String>,
+    F: FnMut(&str, &mut Args<I>) -> Result<bool, String>,
+{
+    let mut args = Args(args);
+    let mut test = false;
+    let mut source = None;
+    while let Some(arg) = args.0.next() {
+        match arg.as_str() {
+            "-t" | "--test" => test = true,
+            _ if arg.starts_with('-') && arg != "-" => {
+                if !option(&arg, &mut args)? {
+                    return Err(format!("Unknown option: {}", arg));
+                }
+            }
+            _ if source.is_some() => return Err(format!("Unexpected argument: {}", arg)),
+            _ => source = Some(Source::from_arg(&arg)),
+        }
+    }
+    match source {
+        Some(_) if test => Err(String::from("--test can't be combined with an input path")),
+        Some(source) => Ok(source),
+        None => Ok(Source::bundled(crate_dir, test)),
+    }
+}
+
+// Parse the arguments (excluding the program name). `None` means that help was requested.
+pub fn parse_args<I: Iterator<Item = String>>(
+    args: I,
+    crate_dir: impl AsRef<Path>,
+) -> Result<Option<Options>, String> {
+    let mut help = false;
+    let mut format = Format::Text;
+    let mut stream = false;
+    let source = parse_command(args, crate_dir, |arg, args| {
+        match arg {
+            "-h" | "--help" => help = true,
+            "-s" | "--stream" => stream = true,
+            "-f" | "--format" => format = args.value(arg)?.parse()?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    });
+    // Help is given if it was asked for before anything in the arguments was wrong.
+    if help {
+        return Ok(None);
+    }
+    Ok(Some(Options {
+        source: source?,
+        format,
+        stream,
+    }))
+}
+
+// The entry point of a day's binary: select and read the input from the command line arguments,
+// solve both parts, and print the answers. In text format, `text` formats each answer given the
+// part it belongs to. Usage or a diagnostic is printed on failure, and the process exits with a
+// non-zero status.
+pub fn run<D: Solver>(crate_dir: &str, text: fn(u32, &str) -> String) {
+    run_with::<D>(crate_dir, text, None);
+}
+
+// The entry point of a day's binary for days that can also solve their input as it is read.
+pub fn run_stream<D: StreamSolver>(crate_dir: &str, text: fn(u32, &str) -> String) {
+    run_with::<D>(
+        crate_dir,
+        text,
+        Some(report::solve_stream::<D, Box<dyn BufRead>>),
+    );
+}
+
+type SolveStream = fn(Box<dyn BufRead>) -> Result<Vec<Report>, StreamError>;
+
+fn run_with<D: Solver>(crate_dir: &str, text: fn(u32, &str) -> String, solve: Option<SolveStream>) {
+    let usage = format!(
+        "Usage: day{:02} [--test] [--format text|json]{} [INPUT | -]\n\n\
+         Solve day {} using INPUT, or stdin if INPUT is `-`. Defaults to data/input.txt, or\n\
+         data/test.txt with --test.{}",
+        D::DAY,
+        if solve.is_some() { " [--stream]" } else { "" },
+        D::DAY,
+        if solve.is_some() {
+            " With --stream the input is solved as it is read, in bounded memory."
+        } else {
+            ""
+        }
+    );
+    let options = match parse_args(std::env::args().skip(1), crate_dir) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", usage);
+            process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("error: {}\n{}", e, usage);
+            process::exit(2);
+        }
+    };
+
+    let name = options.source.name();
+    let reports = match solve.filter(|_| options.stream) {
+        Some(solve) => {
+            let reader = options.source.open().unwrap_or_else(|e| {
+                eprintln!("error: could not read {}: {}", name.display(), e);
+                process::exit(1);
+            });
+            solve(reader).unwrap_or_else(|e| {
+                match e {
+                    StreamError::Io(e) => {
+                        eprintln!("error: could not read {}: {}", name.display(), e)
+                    }
+                    StreamError::Parse(e) => eprintln!("error: {}", e.in_file(&name)),
+                    StreamError::NoAnswer(e) => eprintln!("error: {}: {}", name.display(), e),
+                }
+                process::exit(1);
+            })
+        }
+        None if options.stream => {
+            eprintln!(
+                "error: day {} can't solve its input as a stream\n{}",
+                D::DAY,
+                usage
+            );
+            process::exit(2);
+        }
+        None => {
+            let input = options.source.read().unwrap_or_else(|e| {
+                eprintln!("error: could not read {}: {}", name.display(), e);
+                process::exit(1);
+            });
+            report::solve::<D>(&input, &[1, 2]).unwrap_or_else(|e| {
+                eprintln!("error: {}", e.in_file(&name));
+                process::exit(1);
+            })
+        }
+    };
+
+    for report in reports {
+        match options.format {
+            Format::Text => println!("{}", text(report.part, &report.answer)),
+            Format::Json => println!("{}", report.to_json()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Option<Source>, String> {
+        parse_args(s.split_whitespace().map(String::from), "/aoc/day01")
+            .map(|options| options.map(|o| o.source))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args(""),
+            Ok(Some(Source::File(PathBuf::from(
+                "/aoc/day01/data/input.txt"
+            ))))
+        );
+        assert_eq!(
+            args("--test"),
+            Ok(Some(Source::File(PathBuf::from(
+                "/aoc/day01/data/test.txt"
+            ))))
+        );
+        assert_eq!(args("-"), Ok(Some(Source::Stdin)));
+        assert_eq!(
+            args("foo.txt"),
+            Ok(Some(Source::File(PathBuf::from("foo.txt"))))
+        );
+        assert_eq!(args("-h"), Ok(None));
+        assert!(args("--test foo.txt").is_err());
+        assert!(args("foo.txt bar.txt").is_err());
+        assert!(args("--verbose").is_err());
+    }
+
+    #[test]
+    fn test_parse_format() {
+        let parse = |s: &str| parse_args(s.split_whitespace().map(String::from), "/aoc/day01");
+        assert_eq!(parse("").unwrap().unwrap().format, Format::Text);
+        assert_eq!(
+            parse("--format json -").unwrap(),
+            Some(Options {
+                source: Source::Stdin,
+                format: Format::Json,
+                stream: false,
+            })
+        );
+        assert!(parse("-s").unwrap().unwrap().stream);
+        assert!(parse("--format xml").is_err());
+        assert!(parse("--format").is_err());
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let parse = |s: &str| {
+            let mut level = 0;
+            let source = parse_command(
+                s.split_whitespace().map(String::from),
+                "/aoc/day01",
+                |arg, args| {
+                    match arg {
+                        "--level" => {
+                            level = args.value(arg)?.parse().map_err(|_| "Invalid level")?
+                        }
+                        _ => return Ok(false),
+                    }
+                    Ok(true)
+                },
+            );
+            source.map(|source| (source, level))
+        };
+        assert_eq!(
+            parse("--level 2 --test"),
+            Ok((Source::bundled("/aoc/day01", true), 2))
+        );
+        assert_eq!(parse("- --level 3"), Ok((Source::Stdin, 3)));
+        assert_eq!(
+            parse("--level"),
+            Err(String::from("Missing value for --level"))
+        );
+        assert_eq!(parse("--level x"), Err(String::from("Invalid level")));
+        assert_eq!(
+            parse("--stream"),
+            Err(String::from("Unknown option: --stream"))
+        );
+        assert!(parse("--test foo.txt").is_err());
+    }
+}
@@ -0,0 +1,148 @@
+// Errors reported while parsing puzzle input.
+
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+// A problem found in the puzzle input, pinpointing where it was found. Parsers that work on a
+// single line only know the column, so the line number (1-based, 0 if unknown) is filled in by the
+// caller that iterates over the lines, and the file name by whoever read the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    // The 1-based character column where the offending text starts.
+    pub column: usize,
+    // The offending text itself. This is empty if something was missing.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    // Report `token` as the offending text in `line`. If `token` is a slice of `line`, its column
+    // is derived from its position; otherwise it is reported as missing from the end of the line.
+    pub fn new(line: &str, token: &str, message: impl Into<String>) -> ParseError {
+        let start = line.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset + token.len() <= line.len() && line.is_char_boundary(offset))
+            .unwrap_or(line.len());
+        ParseError {
+            file: None,
+            line: 0,
+            column: line[..offset].chars().count() + 1,
+            text: token.to_string(),
+            message: message.into(),
+        }
+    }
+
+    // Report that something expected was missing at the end of `line`.
+    pub fn missing(line: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(line, &line[line.len()..], message)
+    }
+
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+
+    pub fn in_file(mut self, file: impl AsRef<Path>) -> ParseError {
+        self.file = Some(file.as_ref().to_path_buf());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:", file.display())?,
+            None => write!(f, "<input>:")?,
+        }
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+        if self.text.is_empty() {
+            write!(f, ", found end of line")
+        } else {
+            write!(f, ", found {:?}", self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// Parse `token` (a slice of `line`) as a value of type `T`, reporting it as the offending text if
+// that fails.
+pub fn parse_field<T: FromStr>(line: &str, token: &str, what: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(line, token, format!("expected {}", what)))
+}
+
+// Take the next token from `tokens`, reporting it as missing from `line` if there isn't one.
+pub fn next_field<'a, I: Iterator<Item = &'a str>>(
+    line: &str,
+    tokens: &mut I,
+    what: &str,
+) -> Result<&'a str, ParseError> {
+    tokens
+        .next()
+        .ok_or_else(|| ParseError::missing(line, format!("expected {}", what)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let line = "2-4,6-x";
+        let e = ParseError::new(line, &line[6..], "expected a number");
+        assert_eq!(e.column, 7);
+        assert_eq!(e.text, "x");
+        assert_eq!(e.line, 0);
+        assert_eq!(e.file, None);
+
+        // Text that isn't part of the line is reported at its end.
+        let e = ParseError::new(line, "foo", "expected a number");
+        assert_eq!(e.column, 8);
+    }
+
+    #[test]
+    fn test_missing() {
+        let e = ParseError::missing("addx", "expected an operand");
+        assert_eq!(e.column, 5);
+        assert_eq!(e.text, "");
+    }
+
+    #[test]
+    fn test_display() {
+        let line = "move 1 from x to 2";
+        let e = parse_field::<usize>(line, &line[12..13], "a stack number")
+            .unwrap_err()
+            .at_line(6)
+            .in_file("data/input.txt");
+        assert_eq!(
+            e.to_string(),
+            "data/input.txt:6:13: expected a stack number, found \"x\""
+        );
+        let e = ParseError::missing("$ cd", "expected a directory").at_line(3);
+        assert_eq!(
+            e.to_string(),
+            "<input>:3:5: expected a directory, found end of line"
+        );
+    }
+
+    #[test]
+    fn test_next_field() {
+        let line = "R 4";
+        let mut tokens = line.split_whitespace();
+        assert_eq!(next_field(line, &mut tokens, "a direction"), Ok("R"));
+        assert_eq!(next_field(line, &mut tokens, "a distance"), Ok("4"));
+        assert_eq!(
+            next_field(line, &mut tokens, "a distance")
+                .unwrap_err()
+                .column,
+            4
+        );
+    }
+}
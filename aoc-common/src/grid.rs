@@ -0,0 +1,225 @@
+// A dense, rectangular grid of cells, indexed by `Point`s where x is the column and y the row.
+
+use crate::point::{Direction, Point};
+use crate::ParseError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    // The cells in row-major order.
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // Parse a character map, with one row per line, converting each character with `cell`. A
+    // character that `cell` rejects is reported as not being `what`, and all rows need to be the
+    // same width.
+    pub fn parse<F>(input: &str, what: &str, mut cell: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        for (i, line) in input.lines().enumerate() {
+            let start = cells.len();
+            for (j, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    ParseError::new(
+                        line,
+                        &line[j..j + c.len_utf8()],
+                        format!("expected {}", what),
+                    )
+                    .at_line(i + 1)
+                })?;
+                cells.push(value);
+            }
+            let row_width = cells.len() - start;
+            if row_width == 0 {
+                return Err(ParseError::missing(line, "expected a row of cells").at_line(i + 1));
+            } else if i == 0 {
+                width = row_width;
+            } else if row_width != width {
+                return Err(ParseError::new(
+                    line,
+                    line,
+                    format!("expected a row of {} cells", width),
+                )
+                .at_line(i + 1));
+            }
+            height += 1;
+        }
+        if width == 0 {
+            return Err(ParseError::missing("", "expected a grid").at_line(1));
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    fn offset(&self, p: Point) -> Option<usize> {
+        if self.contains(p) {
+            Some(p.y as usize * self.width + p.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.cells[i])
+    }
+
+    // All points in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    // The orthogonal neighbours of `p` that are inside the grid.
+    pub fn neighbours(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |dir| p + dir)
+            .filter(|&q| self.contains(q))
+    }
+
+    // The points visited when moving from `from` (exclusive) in direction `dir`, up to the edge of
+    // the grid.
+    pub fn ray(&self, from: Point, dir: Direction) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(Some(from + dir), move |&p| Some(p + dir))
+            .take_while(|&p| self.contains(p))
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{} is outside the grid", p))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{} is outside the grid", p))
+    }
+}
+
+// Render the grid with one line per row, and no separators between cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, "a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(0, 0)], 1);
+        assert_eq!(grid[Point::new(2, 1)], 6);
+        assert_eq!(grid.to_string(), "123\n456");
+
+        let e = digits("123\n4x6\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "x"));
+        let e = digits("123\n45\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "45"));
+        let e = digits("\n123\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 1));
+        assert!(digits("").is_err());
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = Grid::new(3, 2, '.');
+        assert!(grid.contains(Point::new(2, 1)));
+        assert!(!grid.contains(Point::new(3, 1)));
+        assert!(!grid.contains(Point::new(0, -1)));
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+        grid[Point::new(1, 0)] = '#';
+        *grid.get_mut(Point::new(2, 1)).unwrap() = '@';
+        assert_eq!(grid.to_string(), ".#.\n..@");
+    }
+
+    #[test]
+    fn test_iterators() {
+        let grid = digits("123\n456\n789\n").unwrap();
+        assert_eq!(grid.row(1).copied().collect::<Vec<_>>(), vec![4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6, 9]);
+        assert_eq!(grid.points().count(), 9);
+        assert_eq!(grid.points().nth(4), Some(Point::new(1, 1)));
+
+        let mut neighbours: Vec<_> = grid.neighbours(Point::new(0, 0)).collect();
+        neighbours.sort();
+        assert_eq!(neighbours, vec![Point::new(0, 1), Point::new(1, 0)]);
+        assert_eq!(grid.neighbours(Point::new(1, 1)).count(), 4);
+
+        let ray: Vec<_> = grid
+            .ray(Point::new(0, 2), Direction::Up)
+            .map(|p| grid[p])
+            .collect();
+        assert_eq!(ray, vec![4, 1]);
+        assert_eq!(grid.ray(Point::new(2, 0), Direction::Right).count(), 0);
+    }
+}
@@ -0,0 +1,56 @@
+// Advent of Code 2022 Common Library
+// Shared infrastructure for the solutions of each day.
+
+pub mod answers;
+pub mod cli;
+pub mod error;
+pub mod grid;
+pub mod point;
+pub mod report;
+pub mod rng;
+pub mod stream;
+pub mod visual;
+
+pub use error::ParseError;
+pub use grid::Grid;
+pub use point::{Direction, Point};
+pub use rng::Rng;
+
+use std::fmt::Display;
+use std::io::BufRead;
+use stream::StreamError;
+
+// Every day's solution follows the same two-step shape: the puzzle text is parsed into a typed
+// model, and each part computes an answer from that model. The answers only need to be printable,
+// so that a runner, test harness or benchmark can treat all days uniformly.
+pub trait Solver {
+    // The day of the Advent calendar that this solution is for.
+    const DAY: u32;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
+
+// Days that can generate valid synthetic puzzle inputs of arbitrary size, for stress testing.
+pub trait Generate: Solver {
+    // Generate an input from `rng`. What `size` measures depends on the day (elves, rounds,
+    // directories, the side of a forest, ...), but the input grows with it.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+// Days whose solution is a simulation that can be watched step by step.
+pub trait Animate: Solver {
+    // The animation of solving `part` from `input`.
+    fn animation(input: &Self::Input, part: u32) -> Box<dyn visual::Animation>;
+}
+
+// Days that can solve both parts while reading the input, in memory that doesn't grow with its
+// length, so that inputs too large to hold in memory can be solved.
+pub trait StreamSolver: Solver {
+    fn solve_stream<R: BufRead>(reader: R) -> Result<(Self::Answer1, Self::Answer2), StreamError>;
+}
@@ -0,0 +1,182 @@
+// Reports of the answers to a day's puzzle, for printing as text or as JSON.
+//
+// In JSON format each answer is printed as an object on its own line (JSON Lines), e.g.
+//
+//     {"day":4,"part":1,"answer":"599","parse_ns":91234,"solve_ns":10432,"input_checksum":"fnv1a64:..."}
+//
+// so that the output of several runs can simply be concatenated.
+
+use crate::stream::{Checksummed, StreamError};
+use crate::{ParseError, Solver, StreamSolver};
+use std::fmt::Write;
+use std::io::BufRead;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    // The time taken to parse the input, which is shared by both parts.
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    pub checksum: u64,
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"parse_ns\":{},\"solve_ns\":{},\
+             \"input_checksum\":\"fnv1a64:{:016x}\"}}",
+            self.day,
+            self.part,
+            json_string(&self.answer),
+            self.parse_time.as_nanos(),
+            self.solve_time.as_nanos(),
+            self.checksum
+        )
+    }
+}
+
+// Parse the input once, and solve each of the requested parts, timing each step.
+pub fn solve<D: Solver>(input: &str, parts: &[u32]) -> Result<Vec<Report>, ParseError> {
+    let start = Instant::now();
+    let model = D::parse(input)?;
+    let parse_time = start.elapsed();
+    let checksum = checksum(input);
+
+    Ok(parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => D::part1(&model).to_string(),
+                _ => D::part2(&model).to_string(),
+            };
+            Report {
+                day: D::DAY,
+                part,
+                answer,
+                parse_time,
+                solve_time: start.elapsed(),
+                checksum,
+            }
+        })
+        .collect())
+}
+
+// Solve both parts in a single pass over `reader`. Reading, parsing and solving are interleaved,
+// so all of the time is reported as parsing, which both parts share.
+pub fn solve_stream<D: StreamSolver, R: BufRead>(reader: R) -> Result<Vec<Report>, StreamError> {
+    let start = Instant::now();
+    let mut reader = Checksummed::new(reader);
+    let (answer1, answer2) = D::solve_stream(&mut reader)?;
+    let parse_time = start.elapsed();
+
+    Ok([answer1.to_string(), answer2.to_string()]
+        .into_iter()
+        .zip(1..)
+        .map(|(answer, part)| Report {
+            day: D::DAY,
+            part,
+            answer,
+            parse_time,
+            solve_time: Duration::ZERO,
+            checksum: reader.checksum(),
+        })
+        .collect())
+}
+
+// The 64-bit FNV-1a hash of the input, to tell which input an answer belongs to.
+pub fn checksum(input: &str) -> u64 {
+    fnv1a(0xcbf29ce484222325, input.as_bytes())
+}
+
+// Continue the FNV-1a hash `hash` with `bytes`.
+pub fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// Quote and escape a string for JSON.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum() {
+        // Reference values of the FNV-1a hash.
+        assert_eq!(checksum(""), 0xcbf29ce484222325);
+        assert_eq!(checksum("a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(checksum("foobar"), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("CMZ"), "\"CMZ\"");
+        assert_eq!(json_string("#.\n.#"), "\"#.\\n.#\"");
+        assert_eq!(json_string("a\"b\\c\u{1}"), "\"a\\\"b\\\\c\\u0001\"");
+    }
+
+    #[test]
+    fn test_to_json() {
+        let report = Report {
+            day: 4,
+            part: 1,
+            answer: String::from("599"),
+            parse_time: Duration::from_nanos(1500),
+            solve_time: Duration::from_nanos(250),
+            checksum: 0xaf63dc4c8601ec8c,
+        };
+        assert_eq!(
+            report.to_json(),
+            "{\"day\":4,\"part\":1,\"answer\":\"599\",\"parse_ns\":1500,\"solve_ns\":250,\
+             \"input_checksum\":\"fnv1a64:af63dc4c8601ec8c\"}"
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("text".parse(), Ok(Format::Text));
+        assert!("csv".parse::<Format>().is_err());
+    }
+}
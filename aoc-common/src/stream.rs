@@ -0,0 +1,216 @@
+// Support for solving a puzzle while its input is being read, for inputs that are too large to
+// hold in memory (see `StreamSolver`).
+
+use crate::report;
+use crate::ParseError;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Read};
+
+// Reading a stream can fail while reading, while parsing what was read, or because input that is
+// well formed has no answer.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+    NoAnswer(String),
+}
+
+impl StreamError {
+    // Report a parse error at `line` of the stream.
+    pub fn at_line(self, line: usize) -> StreamError {
+        match self {
+            StreamError::Parse(e) => StreamError::Parse(e.at_line(line)),
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "{}", e),
+            StreamError::Parse(e) => write!(f, "{}", e),
+            StreamError::NoAnswer(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> StreamError {
+        StreamError::Io(e)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> StreamError {
+        StreamError::Parse(e)
+    }
+}
+
+// Call `f` with each line of `reader` and its 1-based line number, like `str::lines` does for a
+// string: the line ending ("\n" or "\r\n") is removed, and a final line ending doesn't start
+// another line. The same buffer is reused for every line, and a parse error from `f` is reported
+// at the line it came from.
+pub fn for_each_line<R, F>(mut reader: R, mut f: F) -> Result<(), StreamError>
+where
+    R: BufRead,
+    F: FnMut(usize, &str) -> Result<(), StreamError>,
+{
+    let mut buffer = String::new();
+    let mut number = 0;
+    loop {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            return Ok(());
+        }
+        number += 1;
+        let line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        f(number, line).map_err(|e| e.at_line(number))?;
+    }
+}
+
+// A reader that computes the checksum of everything read through it (see `report::checksum`).
+pub struct Checksummed<R> {
+    inner: R,
+    checksum: u64,
+}
+
+impl<R> Checksummed<R> {
+    pub fn new(inner: R) -> Checksummed<R> {
+        Checksummed {
+            inner,
+            checksum: report::checksum(""),
+        }
+    }
+
+    // The checksum of the input read so far.
+    pub fn checksum(&self) -> u64 {
+        self.checksum
+    }
+}
+
+impl<R: Read> Read for Checksummed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.checksum = report::fnv1a(self.checksum, &buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Checksummed<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // Nothing is consumed at the end of the input, and filling the buffer again there would
+        // read past it (waiting for another end of file on a terminal). A buffer that isn't empty
+        // is still filled with what the caller has just seen, which is returned without reading,
+        // so that can't fail.
+        if amt > 0 {
+            if let Ok(buf) = self.inner.fill_buf() {
+                self.checksum = report::fnv1a(self.checksum, &buf[..amt]);
+            }
+        }
+        self.inner.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &str) -> Vec<(usize, String)> {
+        let mut lines = Vec::new();
+        for_each_line(input.as_bytes(), |number, line| {
+            lines.push((number, line.to_string()));
+            Ok(())
+        })
+        .unwrap();
+        lines
+    }
+
+    #[test]
+    fn test_for_each_line() {
+        for input in ["a\n\nbc\r\nd", "a\n\nbc\nd\n", "a\r\n\r\nbc\r\nd\r\n"] {
+            let expected: Vec<(usize, String)> = input
+                .lines()
+                .enumerate()
+                .map(|(i, line)| (i + 1, line.to_string()))
+                .collect();
+            assert_eq!(lines(input), expected);
+        }
+        assert_eq!(lines(""), vec![]);
+
+        let e = for_each_line("12\nx3\n".as_bytes(), |_, line| {
+            crate::error::parse_field::<u32>(line, line, "a number")?;
+            Ok(())
+        })
+        .unwrap_err();
+        match e {
+            StreamError::Parse(e) => assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "x3")),
+            e => panic!("unexpected error: {}", e),
+        }
+        let e = for_each_line(&[b'a', 0xff, b'\n'][..], |_, _| Ok(())).unwrap_err();
+        assert!(matches!(e, StreamError::Io(_)));
+    }
+
+    // A reader that counts how many times it's asked to read at the end of its chunks.
+    struct Chunks {
+        chunks: Vec<&'static [u8]>,
+        ends: usize,
+    }
+
+    impl Read for Chunks {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.fill_buf()?.read(buf)?;
+            self.consume(n);
+            Ok(n)
+        }
+    }
+
+    impl BufRead for Chunks {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            if self.chunks.is_empty() {
+                self.ends += 1;
+                return Ok(&[]);
+            }
+            Ok(self.chunks[0])
+        }
+
+        fn consume(&mut self, amt: usize) {
+            if let Some(chunk) = self.chunks.first_mut() {
+                *chunk = &chunk[amt..];
+                if chunk.is_empty() {
+                    self.chunks.remove(0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_checksummed() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n";
+        let mut reader = Checksummed::new(io::BufReader::with_capacity(7, input.as_bytes()));
+        for_each_line(&mut reader, |_, _| Ok(())).unwrap();
+        assert_eq!(reader.checksum(), report::checksum(input));
+
+        // The end of the input is only read once.
+        let mut reader = Checksummed::new(Chunks {
+            chunks: vec![&input.as_bytes()[..10], &input.as_bytes()[10..]],
+            ends: 0,
+        });
+        for_each_line(&mut reader, |_, _| Ok(())).unwrap();
+        assert_eq!(reader.checksum(), report::checksum(input));
+        assert_eq!(reader.inner.ends, 1);
+
+        let mut reader = Checksummed::new(input.as_bytes());
+        let mut read = String::new();
+        reader.read_to_string(&mut read).unwrap();
+        assert_eq!(reader.checksum(), report::checksum(input));
+    }
+}
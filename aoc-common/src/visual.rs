@@ -0,0 +1,80 @@
+// Step-by-step visualisation of the simulations in some days' puzzles. A day provides an
+// `Animation`, which renders its current state as a frame of text and advances one step at a time,
+// and a player shows the frames: interactively in a terminal (see `aoc animate`), or written one
+// after another to a file.
+
+use std::io::{self, Write};
+
+pub trait Animation {
+    // Render the current state.
+    fn frame(&self) -> String;
+
+    // Advance by one step, and return whether there was a step to take.
+    fn step(&mut self) -> bool;
+
+    // How many steps have been taken so far.
+    fn steps(&self) -> usize;
+}
+
+// The line that separates frames in a dump, and precedes each one.
+fn separator(step: usize) -> String {
+    format!("--- step {} ---", step)
+}
+
+// Write every frame of `animation`, from its current state until it finishes, each preceded by a
+// separator line. At most `limit` steps are taken. Returns the number of frames written.
+pub fn dump<W: Write>(
+    animation: &mut dyn Animation,
+    out: &mut W,
+    limit: usize,
+) -> io::Result<usize> {
+    let mut frames = 0;
+    loop {
+        writeln!(out, "{}", separator(animation.steps()))?;
+        writeln!(out, "{}", animation.frame())?;
+        frames += 1;
+        if frames > limit || !animation.step() {
+            return Ok(frames);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts down from a number, one per step.
+    struct Countdown(usize, usize);
+
+    impl Animation for Countdown {
+        fn frame(&self) -> String {
+            "*".repeat(self.0)
+        }
+
+        fn step(&mut self) -> bool {
+            if self.0 == 0 {
+                return false;
+            }
+            self.0 -= 1;
+            self.1 += 1;
+            true
+        }
+
+        fn steps(&self) -> usize {
+            self.1
+        }
+    }
+
+    #[test]
+    fn test_dump() {
+        let mut out = Vec::new();
+        assert_eq!(dump(&mut Countdown(2, 0), &mut out, 100).unwrap(), 3);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "--- step 0 ---\n**\n--- step 1 ---\n*\n--- step 2 ---\n\n"
+        );
+
+        let mut out = Vec::new();
+        assert_eq!(dump(&mut Countdown(50, 0), &mut out, 3).unwrap(), 4);
+    }
+}
@@ -0,0 +1,249 @@
+// Run every day at once, e.g. `aoc all --budget 1000`. The days are solved concurrently on a
+// pool of worker threads, and the answers are summarised in a table sorted by runtime (slowest
+// first), with the total runtime of the calendar. With `--budget` the run fails if the total is
+// over the given number of milliseconds.
+
+use crate::days;
+use crate::Solve;
+use aoc_common::cli::Source;
+use aoc_common::report::{Format, Report};
+use std::io::ErrorKind;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub test: bool,
+    pub threads: usize,
+    pub budget: Option<Duration>,
+    pub format: Format,
+}
+
+// Parse the arguments following `aoc all`.
+pub fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut options = Options {
+        test: false,
+        threads: thread::available_parallelism().map_or(1, |n| n.get()),
+        budget: None,
+        format: Format::Text,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--test" | "-t" => options.test = true,
+            "--threads" | "-j" => {
+                let v = value()?;
+                options.threads = match v.parse() {
+                    Ok(threads) if threads > 0 => threads,
+                    _ => return Err(format!("Invalid number of threads: {}", v)),
+                };
+            }
+            "--budget" | "-b" => {
+                let v = value()?;
+                let ms = v.parse().map_err(|_| format!("Invalid budget: {}", v))?;
+                options.budget = Some(Duration::from_millis(ms));
+            }
+            "--format" | "-f" => options.format = value()?.parse()?,
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+// The outcome of running a single day.
+enum Outcome {
+    Solved(Vec<Report>),
+    Skipped,
+    Failed(String),
+}
+
+// Read the bundled input of `day` and solve both parts.
+fn run_day(day: u32, test: bool) -> Outcome {
+    let source = Source::bundled(days::crate_dir(day), test);
+    let input = match source.read() {
+        Ok(input) => input,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Outcome::Skipped,
+        Err(e) => {
+            return Outcome::Failed(format!("could not read {}: {}", source.name().display(), e))
+        }
+    };
+    let solve = Solve {
+        input: &input,
+        parts: &[1, 2],
+    };
+    match days::visit(day, solve) {
+        Ok(reports) => Outcome::Solved(reports),
+        Err(e) => Outcome::Failed(e.in_file(source.name()).to_string()),
+    }
+}
+
+// Run each of `days` on a pool of `threads` workers, which take the next day as they become free.
+// The results come back in the order they finish.
+fn run_pool(days: &[u32], threads: usize, test: bool) -> Vec<(u32, Outcome)> {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..threads.min(days.len()) {
+            let tx = tx.clone();
+            let next = &next;
+            scope.spawn(move || {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    tx.send((day, run_day(day, test))).unwrap();
+                }
+            });
+        }
+    });
+    drop(tx);
+    rx.into_iter().collect()
+}
+
+// The time taken by a day: parsing once, and solving each part.
+fn runtime(reports: &[Report]) -> Duration {
+    reports.first().map_or(Duration::ZERO, |r| r.parse_time)
+        + reports.iter().map(|r| r.solve_time).sum::<Duration>()
+}
+
+pub fn run(options: &Options) {
+    let days: Vec<u32> = days::DAYS.collect();
+    let start = Instant::now();
+    let results = run_pool(&days, options.threads, options.test);
+    let wall = start.elapsed();
+
+    let mut solved = Vec::new();
+    let mut failed = false;
+    for (day, outcome) in results {
+        match outcome {
+            Outcome::Solved(reports) => solved.push((day, reports)),
+            Outcome::Skipped => eprintln!("day{:02}: skipping, input not found", day),
+            Outcome::Failed(e) => {
+                eprintln!("day{:02}: error: {}", day, e);
+                failed = true;
+            }
+        }
+    }
+    solved.sort_by_key(|(day, reports)| (std::cmp::Reverse(runtime(reports)), *day));
+    let total: Duration = solved.iter().map(|(_, reports)| runtime(reports)).sum();
+
+    match options.format {
+        Format::Text => print_table(&solved, total, wall, options.threads),
+        Format::Json => {
+            for report in solved.iter().flat_map(|(_, reports)| reports) {
+                println!("{}", report.to_json());
+            }
+        }
+    }
+
+    if let Some(budget) = options.budget {
+        if total > budget {
+            eprintln!(
+                "error: the total runtime of {:.1?} is over the budget of {:.1?}",
+                total, budget
+            );
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+// Answers that span several lines (such as rendered images) don't fit in the table.
+fn summarise(answer: &str) -> String {
+    match answer.lines().count() {
+        0 | 1 => answer.to_string(),
+        n => format!("<{} lines>", n),
+    }
+}
+
+fn print_table(solved: &[(u32, Vec<Report>)], total: Duration, wall: Duration, threads: usize) {
+    println!(
+        "{:>3}  {:<12}  {:<12}  {:>10}",
+        "day", "part 1", "part 2", "runtime"
+    );
+    for (day, reports) in solved {
+        let answer = |part| {
+            reports
+                .iter()
+                .find(|r| r.part == part)
+                .map_or(String::new(), |r| summarise(&r.answer))
+        };
+        println!(
+            "{:>3}  {:<12}  {:<12}  {:>10}",
+            day,
+            answer(1),
+            answer(2),
+            format!("{:.1?}", runtime(reports))
+        );
+    }
+    // The totals span both answer columns.
+    let plural = if threads == 1 { "" } else { "s" };
+    println!(
+        "{:>3}  {:<26}  {:>10}",
+        "",
+        "total",
+        format!("{:.1?}", total)
+    );
+    println!(
+        "{:>3}  {:<26}  {:>10}",
+        "",
+        format!("wall clock ({} thread{})", threads, plural),
+        format!("{:.1?}", wall)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Options, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = args("--test -j 3 --budget 250 --format json").unwrap();
+        assert_eq!(
+            options,
+            Options {
+                test: true,
+                threads: 3,
+                budget: Some(Duration::from_millis(250)),
+                format: Format::Json,
+            }
+        );
+        assert!(args("").unwrap().threads > 0);
+        assert!(args("--threads 0").is_err());
+        assert!(args("--budget soon").is_err());
+        assert!(args("--day 1").is_err());
+    }
+
+    #[test]
+    fn test_run_pool() {
+        // Every day has a bundled sample, and each is run exactly once.
+        let days: Vec<u32> = days::DAYS.collect();
+        let mut results = run_pool(&days, 4, true);
+        assert_eq!(results.len(), days.len());
+        results.sort_by_key(|(day, _)| *day);
+        for ((day, outcome), expected) in results.iter().zip(&days) {
+            assert_eq!(day, expected);
+            match outcome {
+                Outcome::Solved(reports) => assert_eq!(reports.len(), 2),
+                _ => panic!("day {} wasn't solved", day),
+            }
+        }
+    }
+
+    #[test]
+    fn test_summarise() {
+        assert_eq!(summarise("CMZ"), "CMZ");
+        assert_eq!(summarise("#.\n.#\n##"), "<3 lines>");
+    }
+}
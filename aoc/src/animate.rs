@@ -0,0 +1,297 @@
+// Watch a simulation step by step, e.g. `aoc animate --day 9 --part 2 --fps 30`. This is available
+// for the days whose solutions are simulations (5, 9 and 10).
+//
+// While playing, space pauses and resumes, `n` (or the right arrow) takes a single step, `+` and
+// `-` double and halve the speed, and `q` (or Esc) quits. With `--dump PATH` the frames are written
+// to a file instead, one after another.
+
+use crate::days;
+use aoc_common::cli::Source;
+use aoc_common::visual::{self, Animation};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, style, terminal};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+// Never take more steps than this when dumping frames, to bound the size of the file.
+const DUMP_LIMIT: usize = 100_000;
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub day: u32,
+    pub part: u32,
+    pub input: Option<Source>,
+    pub test: bool,
+    pub fps: f64,
+    pub paused: bool,
+    pub dump: Option<PathBuf>,
+}
+
+// Parse the arguments following `aoc animate`.
+pub fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut day = None;
+    let mut options = Options {
+        day: 0,
+        part: 1,
+        input: None,
+        test: false,
+        fps: 10.0,
+        paused: false,
+        dump: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(days::parse_day(&value()?)?),
+            "--part" | "-p" => {
+                let v = value()?;
+                options.part = match v.parse() {
+                    Ok(part @ (1 | 2)) => part,
+                    _ => return Err(format!("Invalid part: {}", v)),
+                };
+            }
+            "--input" | "-i" => options.input = Some(Source::from_arg(&value()?)),
+            "--test" | "-t" => options.test = true,
+            "--fps" => {
+                let v = value()?;
+                options.fps = match v.parse() {
+                    Ok(fps) if fps > 0.0 => fps,
+                    _ => return Err(format!("Invalid frame rate: {}", v)),
+                };
+            }
+            "--paused" => options.paused = true,
+            "--dump" => options.dump = Some(PathBuf::from(value()?)),
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    options.day = day.ok_or("Missing --day")?;
+    if options.input.is_some() && options.test {
+        return Err(String::from("--test can't be combined with --input"));
+    }
+    if options.input == Some(Source::Stdin) && options.dump.is_none() {
+        return Err(String::from(
+            "the input can't come from stdin while playing",
+        ));
+    }
+    Ok(options)
+}
+
+pub fn run(options: &Options) -> Result<(), String> {
+    let source = options
+        .input
+        .clone()
+        .unwrap_or_else(|| Source::bundled(days::crate_dir(options.day), options.test));
+    let input = source
+        .read()
+        .map_err(|e| format!("could not read {}: {}", source.name().display(), e))?;
+    let mut animation = days::animation(options.day, &input, options.part)
+        .ok_or_else(|| format!("day {} has no animation", options.day))?
+        .map_err(|e| e.in_file(source.name()).to_string())?;
+
+    match &options.dump {
+        Some(path) => {
+            let file = File::create(path)
+                .map_err(|e| format!("could not create {}: {}", path.display(), e))?;
+            let mut out = BufWriter::new(file);
+            visual::dump(animation.as_mut(), &mut out, DUMP_LIMIT)
+                .and_then(|_| out.flush())
+                .map_err(|e| format!("could not write {}: {}", path.display(), e))
+        }
+        None => {
+            let mut player = Player {
+                fps: options.fps,
+                paused: options.paused,
+            };
+            play(animation.as_mut(), &mut player).map_err(|e| format!("terminal: {}", e))
+        }
+    }
+}
+
+// What to do after a key press.
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Continue,
+    Step,
+    Quit,
+}
+
+// The playback controls.
+#[derive(Debug, PartialEq)]
+struct Player {
+    fps: f64,
+    paused: bool,
+}
+
+impl Player {
+    fn handle(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+            KeyCode::Char(' ') => {
+                self.paused = !self.paused;
+                Action::Continue
+            }
+            KeyCode::Char('n') | KeyCode::Right => {
+                self.paused = true;
+                Action::Step
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.fps = (self.fps * 2.0).min(1000.0);
+                Action::Continue
+            }
+            KeyCode::Char('-') => {
+                self.fps = (self.fps / 2.0).max(0.25);
+                Action::Continue
+            }
+            _ => Action::Continue,
+        }
+    }
+
+    fn status(&self, steps: usize, finished: bool) -> String {
+        let state = if finished {
+            "finished"
+        } else if self.paused {
+            "paused"
+        } else {
+            "playing"
+        };
+        format!(
+            "step {}, {}, {} fps    [space] pause  [n] step  [+/-] speed  [q] quit",
+            steps, state, self.fps
+        )
+    }
+}
+
+fn play(animation: &mut dyn Animation, player: &mut Player) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = play_frames(animation, player, &mut stdout);
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+
+    // Leave the last frame on the screen.
+    println!("{}", animation.frame());
+    result
+}
+
+fn play_frames<W: Write>(
+    animation: &mut dyn Animation,
+    player: &mut Player,
+    out: &mut W,
+) -> io::Result<()> {
+    let mut finished = false;
+    loop {
+        // In raw mode, each line needs a carriage return.
+        let frame = animation.frame().replace('\n', "\r\n");
+        let status = player.status(animation.steps(), finished);
+        queue!(
+            out,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(terminal::ClearType::All),
+            style::Print(frame),
+            style::Print("\r\n\r\n"),
+            style::Print(status)
+        )?;
+        out.flush()?;
+
+        // Wait for a key press until the next frame is due, or indefinitely when there won't be
+        // one.
+        let waiting = player.paused || finished;
+        let key = if waiting || event::poll(Duration::from_secs_f64(1.0 / player.fps))? {
+            Some(event::read()?)
+        } else {
+            None
+        };
+        let action = match key {
+            Some(Event::Key(key)) if key.kind == KeyEventKind::Press => player.handle(key),
+            // Redraw after anything else, such as a resize.
+            Some(_) => Action::Continue,
+            None => Action::Step,
+        };
+        match action {
+            Action::Quit => return Ok(()),
+            Action::Step if !finished => finished = !animation.step(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Options, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args("--day 9 --part 2 --test --fps 30 --paused --dump rope.txt"),
+            Ok(Options {
+                day: 9,
+                part: 2,
+                input: None,
+                test: true,
+                fps: 30.0,
+                paused: true,
+                dump: Some(PathBuf::from("rope.txt")),
+            })
+        );
+        assert_eq!(args("-d 5").unwrap().fps, 10.0);
+        assert!(args("-d 5 --fps 0").is_err());
+        assert!(args("-d 5 --part 3").is_err());
+        assert!(args("-d 5 -i -").is_err());
+        assert!(args("-d 5 -i - --dump out.txt").is_ok());
+        assert!(args("--fps 5").is_err());
+    }
+
+    #[test]
+    fn test_player() {
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let mut player = Player {
+            fps: 10.0,
+            paused: false,
+        };
+        assert_eq!(player.handle(key(' ')), Action::Continue);
+        assert!(player.paused);
+        assert_eq!(player.handle(key(' ')), Action::Continue);
+        assert!(!player.paused);
+        assert_eq!(player.handle(key('n')), Action::Step);
+        assert!(player.paused);
+        player.handle(key('+'));
+        assert_eq!(player.fps, 20.0);
+        player.handle(key('-'));
+        player.handle(key('-'));
+        assert_eq!(player.fps, 5.0);
+        assert_eq!(player.handle(key('q')), Action::Quit);
+        assert_eq!(
+            player.handle(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Action::Quit
+        );
+        assert!(player.status(3, false).starts_with("step 3, paused, 5 fps"));
+    }
+
+    #[test]
+    fn test_animations() {
+        // The days with animations can play their samples to the end.
+        for day in [5, 9, 10] {
+            let input = Source::bundled(days::crate_dir(day), true).read().unwrap();
+            for part in [1, 2] {
+                let mut animation = days::animation(day, &input, part).unwrap().unwrap();
+                let mut out = Vec::new();
+                let frames = visual::dump(animation.as_mut(), &mut out, DUMP_LIMIT).unwrap();
+                assert_eq!(frames, animation.steps() + 1);
+            }
+        }
+        assert!(days::animation(1, "", 1).is_none());
+    }
+}
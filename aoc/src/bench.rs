@@ -0,0 +1,233 @@
+// Benchmark harness: time parsing, part 1 and part 2 of each day separately, repeated over a
+// number of runs, and report the minimum, median and maximum of each. Results can be printed as a
+// table for reading, or as CSV to compare branches. Build with `--release` for useful numbers.
+
+use crate::days::{self, Visitor};
+use aoc_common::cli::Source;
+use aoc_common::{Generate, ParseError};
+use std::hint::black_box;
+use std::io::ErrorKind;
+use std::process;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Csv,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub days: Vec<u32>,
+    pub runs: usize,
+    pub test: bool,
+    pub format: Format,
+}
+
+// Parse the arguments following `aoc bench`.
+pub fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut options = Options {
+        days: days::DAYS.collect(),
+        runs: 20,
+        test: false,
+        format: Format::Table,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--day" | "-d" => options.days = vec![days::parse_day(&value()?)?],
+            "--runs" | "-r" => {
+                let v = value()?;
+                options.runs = match v.parse() {
+                    Ok(runs) if runs > 0 => runs,
+                    _ => return Err(format!("Invalid number of runs: {}", v)),
+                };
+            }
+            "--format" | "-f" => {
+                options.format = match value()?.as_str() {
+                    "table" => Format::Table,
+                    "csv" => Format::Csv,
+                    other => return Err(format!("Unknown format: {}", other)),
+                }
+            }
+            "--test" | "-t" => options.test = true,
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+// Summary statistics over the samples of a single stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[n - 1],
+        }
+    }
+}
+
+// The stages that are timed for each day, in the order they run.
+const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+struct Bench<'a> {
+    input: &'a str,
+    runs: usize,
+}
+
+impl Visitor for Bench<'_> {
+    type Output = Result<[Stats; 3], ParseError>;
+
+    fn visit<D: Generate>(self) -> Self::Output {
+        let mut samples = [(); 3].map(|_| Vec::with_capacity(self.runs));
+        for _ in 0..self.runs {
+            let start = Instant::now();
+            let model = D::parse(black_box(self.input))?;
+            samples[0].push(start.elapsed());
+
+            let start = Instant::now();
+            black_box(D::part1(black_box(&model)));
+            samples[1].push(start.elapsed());
+
+            let start = Instant::now();
+            black_box(D::part2(black_box(&model)));
+            samples[2].push(start.elapsed());
+        }
+        Ok(samples.map(Stats::from_samples))
+    }
+}
+
+pub fn run(options: &Options) {
+    let mut results = Vec::new();
+    for &day in &options.days {
+        let source = Source::bundled(days::crate_dir(day), options.test);
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                eprintln!(
+                    "day{:02}: skipping, {} not found",
+                    day,
+                    source.name().display()
+                );
+                continue;
+            }
+            Err(e) => {
+                eprintln!("error: could not read {}: {}", source.name().display(), e);
+                process::exit(1);
+            }
+        };
+        let bench = Bench {
+            input: &input,
+            runs: options.runs,
+        };
+        match days::visit(day, bench) {
+            Ok(stats) => results.push((day, stats)),
+            Err(e) => {
+                eprintln!("error: {}", e.in_file(source.name()));
+                process::exit(1);
+            }
+        }
+    }
+
+    match options.format {
+        Format::Table => print_table(&results, options.runs),
+        Format::Csv => print_csv(&results, options.runs),
+    }
+}
+
+fn print_table(results: &[(u32, [Stats; 3])], runs: usize) {
+    println!(
+        "{:>3}  {:<5}  {:>5}  {:>10}  {:>10}  {:>10}",
+        "day", "stage", "runs", "min", "median", "max"
+    );
+    for (day, stats) in results {
+        for (stage, s) in STAGES.iter().zip(stats) {
+            println!(
+                "{:>3}  {:<5}  {:>5}  {:>10}  {:>10}  {:>10}",
+                day,
+                stage,
+                runs,
+                format!("{:.1?}", s.min),
+                format!("{:.1?}", s.median),
+                format!("{:.1?}", s.max)
+            );
+        }
+    }
+}
+
+fn print_csv(results: &[(u32, [Stats; 3])], runs: usize) {
+    println!("day,stage,runs,min_ns,median_ns,max_ns");
+    for (day, stats) in results {
+        for (stage, s) in STAGES.iter().zip(stats) {
+            println!(
+                "{},{},{},{},{},{}",
+                day,
+                stage,
+                runs,
+                s.min.as_nanos(),
+                s.median.as_nanos(),
+                s.max.as_nanos()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Options, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = args("").unwrap();
+        assert_eq!(options.days, (1..=10).collect::<Vec<_>>());
+        assert_eq!(options.format, Format::Table);
+        let options = args("--day 7 --runs 5 --format csv --test").unwrap();
+        assert_eq!(
+            options,
+            Options {
+                days: vec![7],
+                runs: 5,
+                test: true,
+                format: Format::Csv,
+            }
+        );
+        assert!(args("--runs 0").is_err());
+        assert!(args("--format json").is_err());
+        assert!(args("--day 12").is_err());
+    }
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::from_samples(vec![ms(5), ms(1), ms(3)]);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.max, ms(5));
+        let stats = Stats::from_samples(vec![ms(4), ms(1), ms(2), ms(8)]);
+        assert_eq!(stats.median, ms(3));
+    }
+}
@@ -0,0 +1,294 @@
+// A small client for the Advent of Code website: download a day's puzzle input, and submit an
+// answer and interpret the verdict. Requests are authenticated with the session cookie of a
+// logged-in browser. The base URL is configurable so that tests can run against a local server.
+
+use std::fmt;
+use std::time::Duration;
+
+pub const YEAR: u32 = 2022;
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+// The site asks automated tools to identify themselves.
+const USER_AGENT: &str = concat!("aoc-2022-runner/", env!("CARGO_PKG_VERSION"));
+
+// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Right,
+    // A wrong answer, possibly with a hint, after which the site won't take another answer for a
+    // while.
+    Wrong { hint: Option<Hint>, wait: Duration },
+    // The previous answer was too recent, and the answer wasn't checked.
+    TooRecent(Duration),
+    // The part has already been solved (or part 1 hasn't been yet), and the answer wasn't checked.
+    AlreadySolved,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Outcome {
+    // How long to wait before submitting another answer.
+    pub fn wait(&self) -> Duration {
+        match *self {
+            Outcome::Wrong { wait, .. } | Outcome::TooRecent(wait) => wait,
+            Outcome::Right | Outcome::AlreadySolved => Duration::ZERO,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Right => write!(f, "That's the right answer!"),
+            Outcome::Wrong { hint, wait } => {
+                write!(f, "That's not the right answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, "; it's too high")?,
+                    Some(Hint::TooLow) => write!(f, "; it's too low")?,
+                    None => {}
+                }
+                write!(f, ". Wait {}s before trying again.", wait.as_secs())
+            }
+            Outcome::TooRecent(wait) => write!(
+                f,
+                "An answer was given too recently; {}s left to wait.",
+                wait.as_secs()
+            ),
+            Outcome::AlreadySolved => write!(f, "This part has already been solved."),
+        }
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    fn url(&self, day: u32, page: &str) -> String {
+        format!("{}/{}/day/{}/{}", self.base_url, YEAR, day, page)
+    }
+
+    // Download the puzzle input for `day`.
+    pub fn fetch_input(&self, day: u32) -> Result<String, String> {
+        let url = self.url(day, "input");
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| request_error(&url, e))?;
+        response
+            .into_string()
+            .map_err(|e| format!("could not read {}: {}", url, e))
+    }
+
+    // Submit `answer` to `part` of `day`, and interpret the response.
+    pub fn submit(&self, day: u32, part: u32, answer: &str) -> Result<Outcome, String> {
+        let url = self.url(day, "answer");
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| request_error(&url, e))?;
+        let page = response
+            .into_string()
+            .map_err(|e| format!("could not read {}: {}", url, e))?;
+        parse_outcome(&page)
+    }
+}
+
+fn request_error(url: &str, e: ureq::Error) -> String {
+    match e {
+        // The site answers 400 to a missing or expired session, and 404 before a puzzle unlocks.
+        ureq::Error::Status(400, _) => format!("{}: the session token was rejected", url),
+        ureq::Error::Status(404, _) => format!("{}: not found (is the puzzle unlocked?)", url),
+        ureq::Error::Status(code, _) => format!("{}: HTTP status {}", url, code),
+        ureq::Error::Transport(e) => format!("{}: {}", url, e),
+    }
+}
+
+// Interpret the page returned after submitting an answer. The verdict is the text of the page's
+// `<article>`.
+pub fn parse_outcome(page: &str) -> Result<Outcome, String> {
+    let text = article_text(page).ok_or("the response has no <article>")?;
+
+    if text.starts_with("That's the right answer") {
+        return Ok(Outcome::Right);
+    }
+    if text.starts_with("That's not the right answer") {
+        let hint = if text.contains("your answer is too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        let wait = text
+            .split_once("lease wait ")
+            .and_then(|(_, rest)| rest.split_once(" before trying again"))
+            .and_then(|(wait, _)| parse_wait(wait))
+            .unwrap_or(Duration::from_secs(60));
+        return Ok(Outcome::Wrong { hint, wait });
+    }
+    if text.starts_with("You gave an answer too recently") {
+        let wait = text
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .and_then(|(wait, _)| parse_wait(wait))
+            .ok_or_else(|| format!("could not find the time left to wait in {:?}", text))?;
+        return Ok(Outcome::TooRecent(wait));
+    }
+    if text.starts_with("You don't seem to be solving the right level") {
+        return Ok(Outcome::AlreadySolved);
+    }
+    Err(format!("unrecognised response: {:?}", text))
+}
+
+// The text of the first `<article>` in `page`, without tags and with runs of whitespace collapsed.
+fn article_text(page: &str) -> Option<String> {
+    let start = page.find("<article")?;
+    let end = start + page[start..].find("</article>")?;
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+// Parse a wait as the site words it: "one minute", "5 minutes", "42s" or "1m 23s".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let minutes = |n: &str| match n {
+        "one" => Some(1),
+        n => n.parse().ok(),
+    };
+    let secs = match words.as_slice() {
+        [n, "minute" | "minutes"] => minutes(n)? * 60,
+        [m, s] => {
+            m.strip_suffix('m')?.parse::<u64>().ok()? * 60
+                + s.strip_suffix('s')?.parse::<u64>().ok()?
+        }
+        [s] => s.strip_suffix('s')?.parse().ok()?,
+        _ => return None,
+    };
+    Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::serve;
+
+    #[test]
+    fn test_parse_outcome() {
+        let minute = Duration::from_secs(60);
+        let recorded = [
+            (include_str!("../data/responses/right.html"), Outcome::Right),
+            (
+                include_str!("../data/responses/wrong.html"),
+                Outcome::Wrong {
+                    hint: None,
+                    wait: minute,
+                },
+            ),
+            (
+                include_str!("../data/responses/too_high.html"),
+                Outcome::Wrong {
+                    hint: Some(Hint::TooHigh),
+                    wait: minute,
+                },
+            ),
+            (
+                include_str!("../data/responses/too_low.html"),
+                Outcome::Wrong {
+                    hint: Some(Hint::TooLow),
+                    wait: 5 * minute,
+                },
+            ),
+            (
+                include_str!("../data/responses/too_recent.html"),
+                Outcome::TooRecent(Duration::from_secs(83)),
+            ),
+            (
+                include_str!("../data/responses/already_solved.html"),
+                Outcome::AlreadySolved,
+            ),
+        ];
+        for (page, outcome) in recorded {
+            assert_eq!(parse_outcome(page), Ok(outcome));
+        }
+        assert!(parse_outcome("<html></html>").is_err());
+        assert!(parse_outcome("<article><p>Something else</p></article>").is_err());
+    }
+
+    #[test]
+    fn test_parse_wait() {
+        assert_eq!(parse_wait("one minute"), Some(Duration::from_secs(60)));
+        assert_eq!(parse_wait("10 minutes"), Some(Duration::from_secs(600)));
+        assert_eq!(parse_wait("42s"), Some(Duration::from_secs(42)));
+        assert_eq!(parse_wait("2m 5s"), Some(Duration::from_secs(125)));
+        assert_eq!(parse_wait("a while"), None);
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let (url, server) = serve(vec![(200, "1000\n2000\n"), (404, "Not Found")]);
+        let client = Client::new(&url, "abc123");
+        assert_eq!(client.fetch_input(1), Ok(String::from("1000\n2000\n")));
+        assert!(client.fetch_input(25).unwrap_err().contains("not found"));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].line, "GET /2022/day/1/input HTTP/1.1");
+        assert_eq!(requests[0].header("cookie"), Some("session=abc123"));
+        assert!(requests[0].header("user-agent").is_some());
+        assert_eq!(requests[1].line, "GET /2022/day/25/input HTTP/1.1");
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, server) = serve(vec![
+            (200, include_str!("../data/responses/too_low.html")),
+            (200, include_str!("../data/responses/right.html")),
+            (400, "Bad Request"),
+        ]);
+        let client = Client::new(&url, "abc123");
+        assert_eq!(
+            client.submit(4, 2, "900"),
+            Ok(Outcome::Wrong {
+                hint: Some(Hint::TooLow),
+                wait: Duration::from_secs(300)
+            })
+        );
+        assert_eq!(client.submit(4, 2, "928"), Ok(Outcome::Right));
+        assert!(client.submit(4, 2, "928").unwrap_err().contains("session"));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].line, "POST /2022/day/4/answer HTTP/1.1");
+        assert_eq!(requests[0].body, "level=2&answer=900");
+        assert_eq!(requests[1].body, "level=2&answer=928");
+    }
+}
@@ -0,0 +1,67 @@
+// The table of days with solutions. Code that needs to work with any day implements `Visitor`,
+// which is called back with the `Solver` of the requested day (which can also generate inputs).
+
+use aoc_common::visual::Animation;
+use aoc_common::{Animate, Generate, ParseError};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+pub const DAYS: RangeInclusive<u32> = 1..=10;
+
+pub trait Visitor {
+    type Output;
+
+    fn visit<D: Generate>(self) -> Self::Output;
+}
+
+pub fn visit<V: Visitor>(day: u32, visitor: V) -> V::Output {
+    match day {
+        1 => visitor.visit::<day01::Day01>(),
+        2 => visitor.visit::<day02::Day02>(),
+        3 => visitor.visit::<day03::Day03>(),
+        4 => visitor.visit::<day04::Day04>(),
+        5 => visitor.visit::<day05::Day05>(),
+        6 => visitor.visit::<day06::Day06>(),
+        7 => visitor.visit::<day07::Day07>(),
+        8 => visitor.visit::<day08::Day08>(),
+        9 => visitor.visit::<day09::Day09>(),
+        10 => visitor.visit::<day10::Day10>(),
+        _ => unreachable!("No solution for day {}", day),
+    }
+}
+
+// The animation of `part` solving `input`, for the days that have one.
+pub fn animation(
+    day: u32,
+    input: &str,
+    part: u32,
+) -> Option<Result<Box<dyn Animation>, ParseError>> {
+    fn animate<D: Animate>(input: &str, part: u32) -> Result<Box<dyn Animation>, ParseError> {
+        Ok(D::animation(&D::parse(input)?, part))
+    }
+
+    match day {
+        5 => Some(animate::<day05::Day05>(input, part)),
+        9 => Some(animate::<day09::Day09>(input, part)),
+        10 => Some(animate::<day10::Day10>(input, part)),
+        _ => None,
+    }
+}
+
+// The directory of the crate for the given day.
+pub fn crate_dir(day: u32) -> PathBuf {
+    // The runner sits next to the days in the workspace.
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join(format!("day{:02}", day))
+}
+
+// Parse a day given on the command line.
+pub fn parse_day(arg: &str) -> Result<u32, String> {
+    match arg.parse() {
+        Ok(day) if DAYS.contains(&day) => Ok(day),
+        Ok(day) => Err(format!("No solution for day {}", day)),
+        Err(_) => Err(format!("Invalid day: {}", arg)),
+    }
+}
@@ -0,0 +1,103 @@
+// Write a synthetic input for a day to stdout, e.g. `aoc generate --day 9 --size 1000000`. The
+// same seed and size always give the same input, so a failure found with one can be reproduced.
+
+use crate::days::{self, Visitor};
+use aoc_common::{Generate, Rng};
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub day: u32,
+    pub seed: u64,
+    pub size: usize,
+}
+
+// Parse the arguments following `aoc generate`.
+pub fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut day = None;
+    let mut seed = 0;
+    let mut size = 1000;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(days::parse_day(&value()?)?),
+            "--seed" | "-s" => {
+                let v = value()?;
+                seed = v.parse().map_err(|_| format!("Invalid seed: {}", v))?;
+            }
+            "--size" | "-n" => {
+                let v = value()?;
+                size = v.parse().map_err(|_| format!("Invalid size: {}", v))?;
+            }
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    Ok(Options {
+        day: day.ok_or("Missing --day")?,
+        seed,
+        size,
+    })
+}
+
+struct Generator {
+    seed: u64,
+    size: usize,
+}
+
+impl Visitor for Generator {
+    type Output = String;
+
+    fn visit<D: Generate>(self) -> String {
+        D::generate(&mut Rng::new(self.seed), self.size)
+    }
+}
+
+pub fn run(options: &Options) {
+    let generator = Generator {
+        seed: options.seed,
+        size: options.size,
+    };
+    print!("{}", days::visit(options.day, generator));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Options, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args("--day 8 --seed 42 --size 5000"),
+            Ok(Options {
+                day: 8,
+                seed: 42,
+                size: 5000,
+            })
+        );
+        assert_eq!(args("-d 1").unwrap().size, 1000);
+        assert!(args("").is_err());
+        assert!(args("-d 1 --seed x").is_err());
+        assert!(args("-d 1 --size -3").is_err());
+    }
+
+    #[test]
+    fn test_generate_solves() {
+        // Every day should be able to parse and solve what it generates.
+        for day in days::DAYS {
+            let input = days::visit(day, Generator { seed: 7, size: 50 });
+            let solve = crate::Solve {
+                input: &input,
+                parts: &[1, 2],
+            };
+            assert!(days::visit(day, solve).is_ok(), "day {}", day);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+// Advent of Code 2022
+// Run the solution for any day from the workspace root, e.g. `aoc --day 7 --part 2`, or all of them
+// in parallel with `aoc all`. Watch the simulations step by step with `aoc animate`, benchmark the
+// solutions with `aoc bench`, write synthetic inputs with `aoc generate`, or download inputs and
+// submit answers with `aoc fetch` and `aoc submit`.
+
+mod all;
+mod animate;
+mod bench;
+mod client;
+mod days;
+mod generate;
+#[cfg(test)]
+mod mock;
+mod online;
+
+use aoc_common::cli::Source;
+use aoc_common::report::{self, Format, Report};
+use aoc_common::{Generate, ParseError};
+use days::Visitor;
+use std::process;
+
+const USAGE: &str = "\
+Usage: aoc --day <N> [--part <1|2>] [--input <PATH> | --test] [--format text|json]
+       aoc all [--test] [--threads <T>] [--budget <MS>] [--format text|json]
+       aoc animate --day <5|9|10> [--part <1|2>] [--input <PATH> | --test] [--fps <F>] [--paused]
+                   [--dump <PATH>]
+       aoc bench [--day <N>] [--runs <R>] [--format table|csv] [--test]
+       aoc generate --day <N> [--seed <S>] [--size <N>]
+       aoc fetch --day <N> [--force]
+       aoc submit --day <N> --part <1|2> [ANSWER]";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    day: u32,
+    part: Option<u32>,
+    input: Option<Source>,
+    test: bool,
+    format: Format,
+}
+
+// Parse the command line arguments (excluding the program name).
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Args, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut test = false;
+    let mut format = Format::Text;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--day" | "-d" => {
+                day = Some(days::parse_day(&value()?)?);
+            }
+            "--part" | "-p" => {
+                let v = value()?;
+                part = Some(v.parse().map_err(|_| format!("Invalid part: {}", v))?);
+            }
+            "--input" | "-i" => input = Some(Source::from_arg(&value()?)),
+            "--test" | "-t" => test = true,
+            "--format" | "-f" => format = value()?.parse()?,
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    let day = day.ok_or("Missing --day")?;
+    if let Some(p) = part {
+        if p != 1 && p != 2 {
+            return Err(format!("Invalid part: {}", p));
+        }
+    }
+
+    if input.is_some() && test {
+        return Err(String::from("--test can't be combined with --input"));
+    }
+
+    Ok(Args {
+        day,
+        part,
+        input,
+        test,
+        format,
+    })
+}
+
+// Parse the input once, and compute the answers to each of the requested parts.
+struct Solve<'a> {
+    input: &'a str,
+    parts: &'a [u32],
+}
+
+impl Visitor for Solve<'_> {
+    type Output = Result<Vec<Report>, ParseError>;
+
+    fn visit<D: Generate>(self) -> Self::Output {
+        report::solve::<D>(self.input, self.parts)
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.peek().map(String::as_str) == Some("all") {
+        args.next();
+        let options = all::parse_args(args).unwrap_or_else(|e| {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        });
+        all::run(&options);
+        return;
+    }
+    if args.peek().map(String::as_str) == Some("animate") {
+        args.next();
+        let options = animate::parse_args(args).unwrap_or_else(|e| {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        });
+        if let Err(e) = animate::run(&options) {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+    if args.peek().map(String::as_str) == Some("bench") {
+        args.next();
+        let options = bench::parse_args(args).unwrap_or_else(|e| {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        });
+        bench::run(&options);
+        return;
+    }
+    if args.peek().map(String::as_str) == Some("generate") {
+        args.next();
+        let options = generate::parse_args(args).unwrap_or_else(|e| {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        });
+        generate::run(&options);
+        return;
+    }
+    if let Some(command) = args.next_if(|a| a == "fetch" || a == "submit") {
+        let command = online::parse_args(&command, args).unwrap_or_else(|e| {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        });
+        if let Err(e) = online::run(&command) {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let source = args
+        .input
+        .unwrap_or_else(|| Source::bundled(days::crate_dir(args.day), args.test));
+    let input = match source.read() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: could not read {}: {}", source.name().display(), e);
+            process::exit(1);
+        }
+    };
+
+    let parts = match args.part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    let solve = Solve {
+        input: &input,
+        parts: &parts,
+    };
+    let reports = match days::visit(args.day, solve) {
+        Ok(reports) => reports,
+        Err(e) => {
+            eprintln!("error: {}", e.in_file(source.name()));
+            process::exit(1);
+        }
+    };
+
+    if args.format == Format::Json {
+        for report in reports {
+            println!("{}", report.to_json());
+        }
+        return;
+    }
+
+    println!("Day {}", args.day);
+    for Report { part, answer, .. } in reports {
+        if answer.contains('\n') {
+            println!("Part {}:\n{}", part, answer);
+        } else {
+            println!("Part {}: {}", part, answer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn args(s: &str) -> Result<Args, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args("--day 7 --part 2 --input foo.txt"),
+            Ok(Args {
+                day: 7,
+                part: Some(2),
+                input: Some(Source::File(PathBuf::from("foo.txt"))),
+                test: false,
+                format: Format::Text,
+            })
+        );
+        assert_eq!(
+            args("-d 1 --test"),
+            Ok(Args {
+                day: 1,
+                part: None,
+                input: None,
+                test: true,
+                format: Format::Text,
+            })
+        );
+        assert_eq!(args("-d 1 -i -").unwrap().input, Some(Source::Stdin));
+        assert!(args("-d 1 -i foo.txt --test").is_err());
+        assert_eq!(args("-d 1 --format json").unwrap().format, Format::Json);
+        assert!(args("-d 1 --format yaml").is_err());
+        assert!(args("").is_err());
+        assert!(args("--day 11").is_err());
+        assert!(args("--day 1 --part 3").is_err());
+        assert!(args("--day").is_err());
+        assert!(args("--day 1 --verbose").is_err());
+    }
+}
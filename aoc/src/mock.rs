@@ -0,0 +1,71 @@
+// A local HTTP server for testing the client: it replays canned responses, one per connection,
+// and records the requests it was sent.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+
+// A recorded request: the request line, the headers (with lowercase names) and the body.
+pub struct Request {
+    pub line: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+// Serve the given responses (status and body) to successive connections on a local port,
+// and return the base URL along with a handle that yields the requests that were made.
+pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<Request>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, response) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let mut headers = Vec::new();
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                match header.trim_end().split_once(": ") {
+                    Some((name, value)) => headers.push((name.to_lowercase(), value.to_string())),
+                    None => break,
+                }
+            }
+            let request = Request {
+                line: line.trim_end().to_string(),
+                headers,
+                body: String::new(),
+            };
+            let length = request
+                .header("content-length")
+                .map_or(0, |n| n.parse().unwrap());
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            requests.push(Request {
+                body: String::from_utf8(content).unwrap(),
+                ..request
+            });
+            write!(
+                stream,
+                "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                response.len(),
+                response
+            )
+            .unwrap();
+        }
+        requests
+    });
+    (url, handle)
+}
@@ -0,0 +1,440 @@
+// The commands that talk to the Advent of Code website:
+//
+//     aoc fetch --day <N> [--force]
+//     aoc submit --day <N> --part <1|2> [ANSWER]
+//
+// `fetch` downloads the puzzle input to the day's `data/input.txt`, unless it is already there.
+// `submit` sends an answer, by default the one computed from `data/input.txt`. Every submission
+// is logged in the day's `data/submissions.txt`, and the log is checked first, so that an answer
+// is never sent again once it is known to be wrong, or while the site asks us to wait. Right
+// answers are recorded in `data/answers.txt` for the regression suite.
+//
+// The session token is taken from the `AOC_SESSION` environment variable, and `AOC_URL` can point
+// the client at another server.
+
+use crate::client::{Client, Hint, Outcome, DEFAULT_URL};
+use crate::days;
+use crate::Solve;
+use aoc_common::answers::{self, Answers};
+use aoc_common::cli::Source;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Fetch {
+        day: u32,
+        force: bool,
+    },
+    Submit {
+        day: u32,
+        part: u32,
+        answer: Option<String>,
+    },
+}
+
+// Parse the arguments following `aoc fetch` or `aoc submit`.
+pub fn parse_args<I: Iterator<Item = String>>(
+    command: &str,
+    mut args: I,
+) -> Result<Command, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut force = false;
+    let mut answer = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(days::parse_day(&value()?)?),
+            "--part" | "-p" if command == "submit" => {
+                let v = value()?;
+                part = match v.parse() {
+                    Ok(p @ (1 | 2)) => Some(p),
+                    _ => return Err(format!("Invalid part: {}", v)),
+                };
+            }
+            "--force" if command == "fetch" => force = true,
+            _ if command == "submit" && !arg.starts_with('-') && answer.is_none() => {
+                answer = Some(arg)
+            }
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    let day = day.ok_or("Missing --day")?;
+    if command == "fetch" {
+        return Ok(Command::Fetch { day, force });
+    }
+    Ok(Command::Submit {
+        day,
+        part: part.ok_or("Missing --part")?,
+        answer,
+    })
+}
+
+pub fn run(command: &Command) -> Result<(), String> {
+    match *command {
+        Command::Fetch { day, force } => {
+            let path = days::crate_dir(day).join("data").join("input.txt");
+            if path.exists() && !force {
+                println!("{} is already there", path.display());
+                return Ok(());
+            }
+            let input = client()?.fetch_input(day)?;
+            std::fs::write(&path, input)
+                .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+            println!("Saved {}", path.display());
+        }
+        Command::Submit {
+            day,
+            part,
+            ref answer,
+        } => {
+            let crate_dir = days::crate_dir(day);
+            let answer = match answer {
+                Some(answer) => answer.clone(),
+                None => solve(&crate_dir, day, part)?,
+            };
+            println!("Day {} part {}: submitting {}", day, part, answer);
+            let outcome = submit(&client()?, &crate_dir, day, part, &answer, now())?;
+            println!("{}", outcome);
+        }
+    }
+    Ok(())
+}
+
+fn client() -> Result<Client, String> {
+    let session = std::env::var("AOC_SESSION")
+        .map_err(|_| "set AOC_SESSION to the session cookie of a logged-in browser")?;
+    let url = std::env::var("AOC_URL").unwrap_or_else(|_| String::from(DEFAULT_URL));
+    Ok(Client::new(&url, session.trim()))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |t| t.as_secs())
+}
+
+// Compute the answer to `part` from the day's puzzle input.
+fn solve(crate_dir: &Path, day: u32, part: u32) -> Result<String, String> {
+    let source = Source::bundled(crate_dir, false);
+    let input = source
+        .read()
+        .map_err(|e| format!("could not read {}: {}", source.name().display(), e))?;
+    let solve = Solve {
+        input: &input,
+        parts: &[part],
+    };
+    let reports = days::visit(day, solve).map_err(|e| e.in_file(source.name()).to_string())?;
+    Ok(reports[0].answer.clone())
+}
+
+// Submit `answer` unless the recorded answers or the submission log already settle it, and
+// update both with the outcome.
+fn submit(
+    client: &Client,
+    crate_dir: &Path,
+    day: u32,
+    part: u32,
+    answer: &str,
+    now: u64,
+) -> Result<Outcome, String> {
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(format!(
+            "{:?} can't be submitted: answers are a single word",
+            answer
+        ));
+    }
+    if let Ok(recorded) = Answers::load(crate_dir) {
+        match recorded.expected("input.txt", part) {
+            Some(expected) if expected == answer => return Ok(Outcome::AlreadySolved),
+            Some(expected) => {
+                return Err(format!(
+                    "part {} was already solved with {}",
+                    part, expected
+                ))
+            }
+            None => {}
+        }
+    }
+
+    let log_path = crate_dir.join("data").join("submissions.txt");
+    let mut log = match std::fs::read_to_string(&log_path) {
+        Ok(text) => Log::parse(&text)
+            .map_err(|(line, e)| format!("{}:{}: {}", log_path.display(), line, e))?,
+        Err(_) => Log::default(),
+    };
+    log.check(part, answer, now)?;
+
+    let outcome = client.submit(day, part, answer)?;
+    log.entries.push(Entry {
+        time: now,
+        part,
+        outcome,
+        answer: answer.to_string(),
+    });
+    std::fs::write(&log_path, log.to_string())
+        .map_err(|e| format!("could not write {}: {}", log_path.display(), e))?;
+    if outcome == Outcome::Right {
+        answers::record(crate_dir, "input.txt", part, answer)
+            .map_err(|e| format!("could not record the answer: {}", e))?;
+    }
+    Ok(outcome)
+}
+
+// The log of submissions for a day. Each line has the form
+//
+//     <unix time> <part> <verdict> <seconds to wait> <answer>
+//
+// where the verdict is one of `right`, `wrong`, `high`, `low`, `recent` or `solved`.
+#[derive(Debug, Default, PartialEq)]
+struct Log {
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Entry {
+    time: u64,
+    part: u32,
+    outcome: Outcome,
+    answer: String,
+}
+
+impl Log {
+    fn parse(text: &str) -> Result<Log, (usize, String)> {
+        let mut entries = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let fields: Vec<&str> = line.splitn(5, ' ').collect();
+            let entry = match fields[..] {
+                [time, part, verdict, wait, answer] => {
+                    let wait = Duration::from_secs(
+                        wait.parse()
+                            .map_err(|_| (i + 1, format!("invalid wait {:?}", wait)))?,
+                    );
+                    Entry {
+                        time: time
+                            .parse()
+                            .map_err(|_| (i + 1, format!("invalid time {:?}", time)))?,
+                        part: part
+                            .parse()
+                            .map_err(|_| (i + 1, format!("invalid part {:?}", part)))?,
+                        outcome: match verdict {
+                            "right" => Outcome::Right,
+                            "wrong" => Outcome::Wrong { hint: None, wait },
+                            "high" => Outcome::Wrong {
+                                hint: Some(Hint::TooHigh),
+                                wait,
+                            },
+                            "low" => Outcome::Wrong {
+                                hint: Some(Hint::TooLow),
+                                wait,
+                            },
+                            "recent" => Outcome::TooRecent(wait),
+                            "solved" => Outcome::AlreadySolved,
+                            _ => return Err((i + 1, format!("invalid verdict {:?}", verdict))),
+                        },
+                        answer: answer.to_string(),
+                    }
+                }
+                _ => {
+                    return Err((
+                        i + 1,
+                        String::from("expected <time> <part> <verdict> <wait> <answer>"),
+                    ))
+                }
+            };
+            entries.push(entry);
+        }
+        Ok(Log { entries })
+    }
+
+    // Check whether `answer` is worth submitting to `part` at time `now`.
+    fn check(&self, part: u32, answer: &str, now: u64) -> Result<(), String> {
+        for e in self.entries.iter().filter(|e| e.part == part) {
+            // Compare numerically with answers that were too high or too low, where possible.
+            let bound = match (answer.parse::<i64>(), e.answer.parse::<i64>()) {
+                (Ok(a), Ok(b)) => Some(a.cmp(&b)),
+                _ => None,
+            };
+            match e.outcome {
+                Outcome::Right => {
+                    return Err(format!(
+                        "part {} was already solved with {}",
+                        part, e.answer
+                    ))
+                }
+                Outcome::Wrong { .. } if e.answer == answer => {
+                    return Err(format!("{} was already rejected", answer))
+                }
+                Outcome::Wrong {
+                    hint: Some(Hint::TooHigh),
+                    ..
+                } if bound.is_some_and(|o| o.is_ge()) => {
+                    return Err(format!(
+                        "{} is too high, as {} already was",
+                        answer, e.answer
+                    ))
+                }
+                Outcome::Wrong {
+                    hint: Some(Hint::TooLow),
+                    ..
+                } if bound.is_some_and(|o| o.is_le()) => {
+                    return Err(format!(
+                        "{} is too low, as {} already was",
+                        answer, e.answer
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(last) = self.entries.last() {
+            let until = last.time + last.outcome.wait().as_secs();
+            if until > now {
+                return Err(format!("wait {}s before submitting again", until - now));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Log {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for e in &self.entries {
+            let verdict = match e.outcome {
+                Outcome::Right => "right",
+                Outcome::Wrong { hint: None, .. } => "wrong",
+                Outcome::Wrong {
+                    hint: Some(Hint::TooHigh),
+                    ..
+                } => "high",
+                Outcome::Wrong {
+                    hint: Some(Hint::TooLow),
+                    ..
+                } => "low",
+                Outcome::TooRecent(_) => "recent",
+                Outcome::AlreadySolved => "solved",
+            };
+            writeln!(
+                f,
+                "{} {} {} {} {}",
+                e.time,
+                e.part,
+                verdict,
+                e.outcome.wait().as_secs(),
+                e.answer
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::serve;
+
+    fn args(command: &str, s: &str) -> Result<Command, String> {
+        parse_args(command, s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args("fetch", "--day 3 --force"),
+            Ok(Command::Fetch {
+                day: 3,
+                force: true
+            })
+        );
+        assert_eq!(
+            args("submit", "-d 5 -p 1 CMZ"),
+            Ok(Command::Submit {
+                day: 5,
+                part: 1,
+                answer: Some(String::from("CMZ"))
+            })
+        );
+        assert_eq!(
+            args("submit", "-d 5 -p 2"),
+            Ok(Command::Submit {
+                day: 5,
+                part: 2,
+                answer: None
+            })
+        );
+        assert!(args("submit", "-d 5").is_err());
+        assert!(args("submit", "-d 5 -p 3").is_err());
+        assert!(args("submit", "-d 5 -p 1 CMZ MCD").is_err());
+        assert!(args("fetch", "-d 5 -p 1").is_err());
+        assert!(args("fetch", "").is_err());
+    }
+
+    #[test]
+    fn test_log() {
+        let text = "1000 1 high 60 500\n1100 1 low 300 100\n1500 1 wrong 60 250\n";
+        let log = Log::parse(text).unwrap();
+        assert_eq!(log.to_string(), text);
+        assert_eq!(
+            log.entries[1].outcome,
+            Outcome::Wrong {
+                hint: Some(Hint::TooLow),
+                wait: Duration::from_secs(300)
+            }
+        );
+        assert_eq!(Log::parse("1000 1 maybe 0 42").unwrap_err().0, 1);
+        assert_eq!(Log::parse("\n").unwrap_err().0, 1);
+
+        assert!(log.check(1, "300", 2000).is_ok());
+        assert!(log.check(1, "250", 2000).is_err());
+        assert!(log.check(1, "500", 2000).is_err());
+        assert!(log.check(1, "700", 2000).is_err());
+        assert!(log.check(1, "50", 2000).is_err());
+        assert!(log.check(1, "300", 1530).is_err());
+        assert!(log.check(2, "300", 2000).is_ok());
+
+        let log = Log::parse("1000 2 right 0 ABC\n").unwrap();
+        assert!(log.check(2, "ABD", 5000).is_err());
+    }
+
+    #[test]
+    fn test_submit() {
+        let crate_dir = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        std::fs::create_dir_all(crate_dir.join("data")).unwrap();
+        let (url, server) = serve(vec![
+            (200, include_str!("../data/responses/too_high.html")),
+            (200, include_str!("../data/responses/right.html")),
+        ]);
+        let client = Client::new(&url, "abc123");
+
+        assert_eq!(
+            submit(&client, &crate_dir, 1, 1, "500", 1000),
+            Ok(Outcome::Wrong {
+                hint: Some(Hint::TooHigh),
+                wait: Duration::from_secs(60)
+            })
+        );
+        // These are settled by the log, without asking the server.
+        assert!(submit(&client, &crate_dir, 1, 1, "600", 2000).is_err());
+        assert!(submit(&client, &crate_dir, 1, 1, "400", 1030).is_err());
+        assert_eq!(
+            submit(&client, &crate_dir, 1, 1, "400", 2000),
+            Ok(Outcome::Right)
+        );
+        assert_eq!(
+            submit(&client, &crate_dir, 1, 1, "400", 3000),
+            Ok(Outcome::AlreadySolved)
+        );
+        assert!(submit(&client, &crate_dir, 1, 2, "two words", 3000).is_err());
+
+        let answers = Answers::load(&crate_dir).unwrap();
+        assert_eq!(answers.expected("input.txt", 1), Some("400"));
+        assert_eq!(server.join().unwrap().len(), 2);
+        std::fs::remove_dir_all(&crate_dir).unwrap();
+    }
+}
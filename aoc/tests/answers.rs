@@ -0,0 +1,112 @@
+// Regression suite: solve every input that has recorded answers in a day's `data/answers.txt`,
+// and compare the results. Inputs that aren't present (puzzle inputs are personal, and not every
+// checkout has them) are skipped.
+
+use aoc_common::answers::Answers;
+use aoc_common::Solver;
+use std::io::ErrorKind;
+use std::path::Path;
+
+fn check<D: Solver>() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{:02}", D::DAY));
+    let answers = Answers::load(&crate_dir).unwrap();
+    let mut failures = Vec::new();
+
+    for file in answers.files() {
+        let path = answers.data_dir.join(file);
+        let input = match std::fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                eprintln!("day{:02}: skipping {}: not found", D::DAY, file);
+                continue;
+            }
+            Err(e) => panic!("could not read {}: {}", path.display(), e),
+        };
+        let model = D::parse(&input).unwrap_or_else(|e| panic!("{}", e.in_file(&path)));
+
+        for part in [1, 2] {
+            let expected = match answers.expected(file, part) {
+                Some(expected) => expected,
+                None => continue,
+            };
+            let actual = match part {
+                1 => D::part1(&model).to_string(),
+                _ => D::part2(&model).to_string(),
+            };
+            if actual != expected {
+                failures.push(format!(
+                    "{} part {}: expected {:?}, got {:?}",
+                    file, part, expected, actual
+                ));
+            }
+        }
+    }
+
+    // The puzzle input is the main thing to guard, so if it's present it needs answers on record.
+    if !answers.files().contains(&"input.txt") {
+        if answers.data_dir.join("input.txt").exists() {
+            failures.push(String::from("input.txt has no recorded answers"));
+        } else {
+            eprintln!("day{:02}: skipping input.txt: not found", D::DAY);
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "day{:02}:\n  {}",
+        D::DAY,
+        failures.join("\n  ")
+    );
+}
+
+#[test]
+fn day01() {
+    check::<day01::Day01>();
+}
+
+#[test]
+fn day02() {
+    check::<day02::Day02>();
+}
+
+#[test]
+fn day03() {
+    check::<day03::Day03>();
+}
+
+#[test]
+fn day04() {
+    check::<day04::Day04>();
+}
+
+#[test]
+fn day05() {
+    check::<day05::Day05>();
+}
+
+#[test]
+fn day06() {
+    check::<day06::Day06>();
+}
+
+#[test]
+fn day07() {
+    check::<day07::Day07>();
+}
+
+#[test]
+fn day08() {
+    check::<day08::Day08>();
+}
+
+#[test]
+fn day09() {
+    check::<day09::Day09>();
+}
+
+#[test]
+fn day10() {
+    check::<day10::Day10>();
+}
@@ -0,0 +1,71 @@
+// Benchmark the selection of the top N elves, comparing the heap in `top_elves` with the linear
+// scan it replaced, on generated inputs:
+//
+//     cargo run --release -p day01 --example top_n -- [ELVES [SEED]]
+//
+// The linear scan keeps the N largest totals in a vector, and looks for the smallest of them again
+// every time it replaces one, which is O(N) per replacement instead of O(log N).
+
+use aoc_common::{Generate, Rng, Solver};
+use day01::{top_elves, Batch, Day01};
+use std::hint::black_box;
+use std::process;
+use std::time::{Duration, Instant};
+
+// The previous implementation, which only found the sum of the totals.
+fn argmax_calories(batches: &[Batch], top_n: usize) -> u64 {
+    let mut max = vec![0; top_n];
+    let mut min_idx = 0;
+    let mut min = 0;
+
+    for sum in batches.iter().map(Batch::total) {
+        if sum > min {
+            max[min_idx] = sum;
+            min_idx = max.iter().enumerate().min_by_key(|(_, &v)| v).unwrap().0;
+            min = max[min_idx];
+        }
+    }
+
+    max.iter().sum()
+}
+
+// The fastest of a few runs of `f`.
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1).map(|arg| {
+        arg.parse().unwrap_or_else(|_| {
+            eprintln!("Usage: top_n [ELVES [SEED]]");
+            process::exit(2);
+        })
+    });
+    let elves = args.next().unwrap_or(2_000_000);
+    let seed = args.next().unwrap_or(0);
+
+    let input = Day01::generate(&mut Rng::new(seed as u64), elves);
+    let batches = Day01::parse(&input).unwrap();
+    println!("{} elves", batches.len());
+    println!("{:>8}  {:>10}  {:>10}", "N", "linear", "heap");
+    for top_n in [1, 3, 10, 100, 1_000, 10_000] {
+        let linear = time(|| argmax_calories(&batches, top_n));
+        let heap = time(|| top_elves(&batches, top_n));
+        // Generated totals are positive, where both agree.
+        let total: u64 = top_elves(&batches, top_n).iter().map(|elf| elf.total).sum();
+        assert_eq!(argmax_calories(&batches, top_n), total);
+        println!(
+            "{:>8}  {:>10}  {:>10}",
+            top_n,
+            format!("{:.1?}", linear),
+            format!("{:.1?}", heap)
+        );
+    }
+}
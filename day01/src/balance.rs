@@ -0,0 +1,418 @@
+// Redistributing the food items among the same elves so that the most heavily laden elf carries
+// as little as possible. This is multiprocessor scheduling: NP-hard in general, so small inputs are
+// solved exactly by branch and bound, and large ones with the LPT heuristic (longest processing
+// time first), which hands out the largest items first, each to the least laden elf. LPT is never
+// worse than 4/3 of the optimum, and the plan reports how far it can be from a lower bound.
+
+use crate::Batch;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+// Up to this many items, `Method::Auto` solves exactly.
+pub const EXACT_ITEMS: usize = 40;
+
+// `Method::Exact` refuses inputs with more items than this, which it would have no hope of solving.
+pub const MAX_EXACT_ITEMS: usize = 1000;
+
+// The exact search gives up after visiting this many nodes, and keeps the best plan found so far.
+const NODE_BUDGET: u64 = 10_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Exact,
+    Greedy,
+    // Exact for small inputs, greedy for large ones.
+    Auto,
+}
+
+// A food item, and where it was listed in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Item {
+    pub elf: usize,
+    pub line: usize,
+    pub calories: u64,
+}
+
+// Giving an item to another elf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub item: Item,
+    pub to: usize,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "move {} calories (line {}) from elf {} to elf {}",
+            self.item.calories,
+            self.item.line,
+            self.item.elf + 1,
+            self.to + 1
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    // The calories carried by each elf before and after. A plan that isn't optimal can give an
+    // elf more than 64 bits' worth, like the totals of `Summary`.
+    pub before: Vec<u128>,
+    pub after: Vec<u128>,
+    // No plan can do better than this.
+    pub lower_bound: u128,
+    // Whether the plan is known to be optimal.
+    pub optimal: bool,
+    pub moves: Vec<Move>,
+}
+
+impl Plan {
+    pub fn max_load(&self) -> u128 {
+        self.after.iter().copied().max().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let before = self.before.iter().copied().max().unwrap_or(0);
+        write!(f, "largest load: {} (was {}), ", self.max_load(), before)?;
+        if self.optimal {
+            writeln!(f, "which is optimal")?;
+        } else {
+            let excess = (self.max_load() - self.lower_bound) as f64 / self.lower_bound as f64;
+            writeln!(
+                f,
+                "at most {:.2}% above the optimum of at least {}",
+                100.0 * excess,
+                self.lower_bound
+            )?;
+        }
+        writeln!(f, "{} moves", self.moves.len())?;
+        for m in &self.moves {
+            writeln!(f, "{}", m)?;
+        }
+        Ok(())
+    }
+}
+
+// Plan how to redistribute the items among the elves. Large inputs are rejected when an exact
+// solution is asked for.
+pub fn plan(batches: &[Batch], method: Method) -> Result<Plan, String> {
+    let mut items = Vec::new();
+    for (elf, batch) in batches.iter().enumerate() {
+        for food in &batch.items {
+            items.push(Item {
+                elf,
+                line: food.line,
+                calories: food.calories,
+            });
+        }
+    }
+    if method == Method::Exact && items.len() > MAX_EXACT_ITEMS {
+        return Err(format!(
+            "{} items are too many to balance exactly (at most {})",
+            items.len(),
+            MAX_EXACT_ITEMS
+        ));
+    }
+    let elves = batches.len();
+    let sizes: Vec<u64> = items.iter().map(|item| item.calories).collect();
+    let lower_bound = lower_bound(&sizes, elves);
+
+    let (bins, optimal) = match method {
+        Method::Greedy => (lpt(&sizes, elves), false),
+        Method::Auto if items.len() > EXACT_ITEMS => (lpt(&sizes, elves), false),
+        Method::Exact | Method::Auto => exact(&sizes, elves),
+    };
+    // The best plan can't do better than the bound, so reaching it proves optimality.
+    let largest = loads(&sizes, &bins, elves).into_iter().max().unwrap_or(0);
+    let optimal = optimal || largest == lower_bound;
+
+    // Any elf can take any of the new loads, so give each to the elf that already holds most of
+    // its items, to keep the number of moves down.
+    let owners = assign_owners(&items, &bins, elves);
+    let moves = items
+        .iter()
+        .zip(&bins)
+        .filter(|(item, &bin)| owners[bin] != item.elf)
+        .map(|(&item, &bin)| Move {
+            item,
+            to: owners[bin],
+        })
+        .collect();
+    let mut before = vec![0; elves];
+    for item in &items {
+        before[item.elf] += item.calories as u128;
+    }
+    let mut after = vec![0; elves];
+    for (item, &bin) in items.iter().zip(&bins) {
+        after[owners[bin]] += item.calories as u128;
+    }
+
+    Ok(Plan {
+        before,
+        after,
+        lower_bound,
+        optimal,
+        moves,
+    })
+}
+
+// No elf can carry less than an even share, or less than the largest item.
+fn lower_bound(sizes: &[u64], elves: usize) -> u128 {
+    let total: u128 = sizes.iter().map(|&size| size as u128).sum();
+    let largest = sizes.iter().copied().max().unwrap_or(0);
+    total.div_ceil(elves.max(1) as u128).max(largest as u128)
+}
+
+// The load of each bin, given the bin of each item.
+fn loads(sizes: &[u64], bins: &[usize], elves: usize) -> Vec<u128> {
+    let mut loads = vec![0; elves];
+    for (&size, &bin) in sizes.iter().zip(bins) {
+        loads[bin] += size as u128;
+    }
+    loads
+}
+
+// The items in decreasing order of size (ties in input order).
+fn by_size(sizes: &[u64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| Reverse(sizes[i]));
+    order
+}
+
+// The bin of each item, handing out the largest items first to the least laden bin.
+fn lpt(sizes: &[u64], elves: usize) -> Vec<usize> {
+    let mut bins = vec![0; sizes.len()];
+    // A min-heap of (load, bin), so ties go to the lowest bin.
+    let mut heap: BinaryHeap<Reverse<(u128, usize)>> =
+        (0..elves).map(|b| Reverse((0, b))).collect();
+    for i in by_size(sizes) {
+        let Reverse((load, bin)) = heap.pop().unwrap();
+        bins[i] = bin;
+        heap.push(Reverse((load + sizes[i] as u128, bin)));
+    }
+    bins
+}
+
+// Search for the best assignment by branch and bound, starting from the LPT plan. Returns the bin
+// of each item, and whether the search was completed within its budget.
+fn exact(sizes: &[u64], elves: usize) -> (Vec<usize>, bool) {
+    struct Search<'a> {
+        sizes: &'a [u64],
+        order: Vec<usize>,
+        loads: Vec<u128>,
+        bins: Vec<usize>,
+        best: Vec<usize>,
+        best_max: u128,
+        lower_bound: u128,
+        nodes: u64,
+    }
+
+    impl Search<'_> {
+        // Place the `k`th largest item, and the ones after it. Returns false once the search can
+        // stop, because the budget ran out or the bound was reached.
+        fn place(&mut self, k: usize, max: u128) -> bool {
+            self.nodes += 1;
+            if self.nodes > NODE_BUDGET {
+                return false;
+            }
+            if k == self.order.len() {
+                self.best_max = max;
+                self.best.clone_from(&self.bins);
+                return max > self.lower_bound;
+            }
+            let item = self.order[k];
+            let size = self.sizes[item] as u128;
+            for bin in 0..self.loads.len() {
+                let load = self.loads[bin] + size;
+                // Only improvements are of interest, and bins that are equally laden are
+                // interchangeable, as are all of the empty ones.
+                if load >= self.best_max || self.loads[..bin].contains(&self.loads[bin]) {
+                    continue;
+                }
+                self.loads[bin] = load;
+                self.bins[item] = bin;
+                let more = self.place(k + 1, max.max(load));
+                self.loads[bin] -= size;
+                if !more {
+                    return false;
+                }
+            }
+            true
+        }
+    }
+
+    let greedy = lpt(sizes, elves);
+    if elves == 0 {
+        return (greedy, true);
+    }
+    let mut search = Search {
+        sizes,
+        order: by_size(sizes),
+        loads: vec![0; elves],
+        bins: vec![0; sizes.len()],
+        best_max: loads(sizes, &greedy, elves).into_iter().max().unwrap(),
+        best: greedy,
+        lower_bound: lower_bound(sizes, elves),
+        nodes: 0,
+    };
+    if search.best_max == search.lower_bound {
+        return (search.best, true);
+    }
+    search.place(0, 0);
+    let complete = search.nodes <= NODE_BUDGET;
+    (search.best, complete)
+}
+
+// Choose the elf that takes each bin, preferring the elf that already holds the most items in it.
+// Pairs are matched greedily from the largest overlap down.
+fn assign_owners(items: &[Item], bins: &[usize], elves: usize) -> Vec<usize> {
+    let mut overlap = std::collections::HashMap::new();
+    for (item, &bin) in items.iter().zip(bins) {
+        *overlap.entry((bin, item.elf)).or_insert(0) += 1;
+    }
+    let mut pairs: Vec<((usize, usize), usize)> = overlap.into_iter().collect();
+    pairs.sort_by_key(|&(pair, count)| (Reverse(count), pair));
+
+    let mut owners = vec![usize::MAX; elves];
+    let mut taken = vec![false; elves];
+    for ((bin, elf), _) in pairs {
+        if owners[bin] == usize::MAX && !taken[elf] {
+            owners[bin] = elf;
+            taken[elf] = true;
+        }
+    }
+    let mut free = (0..elves).filter(|&elf| !taken[elf]);
+    for owner in owners.iter_mut().filter(|owner| **owner == usize::MAX) {
+        *owner = free.next().unwrap();
+    }
+    owners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_batches, Food};
+    use proptest::prelude::*;
+
+    const TEST_INPUT: &str = include_str!("../data/test.txt");
+
+    #[test]
+    fn test_plan() {
+        let batches = parse_batches(TEST_INPUT).unwrap();
+        // 55000 calories among 5 elves, in items of up to 10000.
+        let plan = plan(&batches, Method::Exact).unwrap();
+        assert_eq!(plan.lower_bound, 11000);
+        assert!(plan.optimal);
+        assert_eq!(plan.max_load(), 11000);
+        assert_eq!(plan.before, vec![6000, 4000, 11000, 24000, 10000]);
+        assert_eq!(plan.after.iter().sum::<u128>(), 55000);
+        // Elves that end up with the same items keep them.
+        for m in &plan.moves {
+            assert_ne!(m.item.elf, m.to);
+        }
+        assert!(plan
+            .to_string()
+            .starts_with("largest load: 11000 (was 24000), which is optimal"));
+    }
+
+    #[test]
+    fn test_lpt() {
+        let sizes = [7, 5, 5, 4, 3, 3];
+        assert_eq!(loads(&sizes, &lpt(&sizes, 3), 3), vec![10, 9, 8]);
+        // Here LPT pairs the two largest items, and only the exact search finds 3+3 and 2+2+2.
+        let sizes = [3, 3, 2, 2, 2];
+        assert_eq!(loads(&sizes, &lpt(&sizes, 2), 2), vec![7, 5]);
+        let (bins, complete) = exact(&sizes, 2);
+        assert!(complete);
+        assert_eq!(loads(&sizes, &bins, 2), vec![6, 6]);
+    }
+
+    #[test]
+    fn test_rejected() {
+        let batches = vec![
+            Batch {
+                items: vec![
+                    Food {
+                        line: 1,
+                        calories: 1
+                    };
+                    MAX_EXACT_ITEMS + 1
+                ],
+            };
+            2
+        ];
+        assert!(plan(&batches, Method::Exact).is_err());
+        assert!(plan(&batches, Method::Auto).is_ok());
+    }
+
+    #[test]
+    fn test_wide() {
+        // The same items as in `test_lpt`, scaled up so that each elf carries nearly 2^64
+        // calories, and LPT gives one of them more than that.
+        let k = u64::MAX / 6;
+        let batch = |calories: &[u64]| Batch {
+            items: calories
+                .iter()
+                .map(|&calories| Food { line: 1, calories })
+                .collect(),
+        };
+        let batches = [batch(&[3 * k, 3 * k]), batch(&[2 * k, 2 * k, 2 * k])];
+        let greedy = plan(&batches, Method::Greedy).unwrap();
+        assert_eq!(greedy.max_load(), 7 * k as u128);
+        assert_eq!(greedy.lower_bound, 6 * k as u128);
+        assert!(!greedy.optimal);
+        let exact = plan(&batches, Method::Exact).unwrap();
+        assert_eq!(exact.max_load(), 6 * k as u128);
+        assert!(exact.optimal);
+        assert_eq!(exact.before, exact.after);
+    }
+
+    // The best possible largest load, by trying every assignment.
+    fn brute_force(sizes: &[u64], elves: usize) -> u128 {
+        let mut best = u128::MAX;
+        let mut bins = vec![0; sizes.len()];
+        loop {
+            best = best.min(loads(sizes, &bins, elves).into_iter().max().unwrap());
+            let Some(i) = bins.iter().position(|&b| b + 1 < elves) else {
+                return best;
+            };
+            bins[i] += 1;
+            bins[..i].fill(0);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_exact(sizes in prop::collection::vec(0..20u64, 1..8), elves in 1..4usize) {
+            let (bins, complete) = exact(&sizes, elves);
+            prop_assert!(complete);
+            let best = loads(&sizes, &bins, elves).into_iter().max().unwrap();
+            prop_assert_eq!(best, brute_force(&sizes, elves));
+            // LPT is within 4/3 of the optimum.
+            let greedy = loads(&sizes, &lpt(&sizes, elves), elves).into_iter().max().unwrap();
+            prop_assert!(3 * greedy <= 4 * best);
+        }
+
+        #[test]
+        fn test_moves(batches in prop::collection::vec(prop::collection::vec(0..100u64, 0..5), 1..6)) {
+            // Applying the moves gives the planned loads.
+            let batches: Vec<Batch> = batches
+                .into_iter()
+                .map(|calories| Batch {
+                    items: calories.into_iter().map(|calories| Food { line: 1, calories }).collect(),
+                })
+                .collect();
+            let plan = plan(&batches, Method::Auto).unwrap();
+            let mut loads = plan.before.clone();
+            for m in &plan.moves {
+                loads[m.item.elf] -= m.item.calories as u128;
+                loads[m.to] += m.item.calories as u128;
+            }
+            prop_assert_eq!(loads, plan.after.clone());
+            prop_assert!(plan.max_load() >= plan.lower_bound);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// Synthetic inputs: `size` elves, each carrying a handful of food items.
+
+use crate::Day01;
+use aoc_common::{Generate, Rng};
+
+impl Generate for Day01 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut input = String::new();
+        for elf in 0..size.max(1) {
+            if elf > 0 {
+                input.push('\n');
+            }
+            for _ in 0..rng.between(1, 15) {
+                input.push_str(&rng.between(1000, 70000).to_string());
+                input.push('\n');
+            }
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_generate() {
+        let input = Day01::generate(&mut Rng::new(1), 100);
+        assert_eq!(input, Day01::generate(&mut Rng::new(1), 100));
+        let batches = Day01::parse(&input).unwrap();
+        assert_eq!(batches.len(), 100);
+        assert!(Day01::part2(&batches) >= Day01::part1(&batches));
+    }
+}
@@ -0,0 +1,446 @@
+// Advent of Code 2022 - Day 1
+// Read batches of numbers from the input file, and rank the batches by their sums.
+
+pub mod balance;
+mod generate;
+pub mod live;
+pub mod stats;
+
+use aoc_common::error::parse_field;
+use aoc_common::{ParseError, Solver};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::ops::Range;
+
+// A food item, and the line (1-based) it was listed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Food {
+    pub line: usize,
+    pub calories: u64,
+}
+
+// The food items carried by one elf.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Batch {
+    pub items: Vec<Food>,
+}
+
+impl Batch {
+    // The lines from the first item to the last, as a half-open range.
+    pub fn lines(&self) -> Range<usize> {
+        match (self.items.first(), self.items.last()) {
+            (Some(first), Some(last)) => first.line..last.line + 1,
+            _ => 0..0,
+        }
+    }
+
+    pub fn calories(&self) -> impl Iterator<Item = u64> + '_ {
+        self.items.iter().map(|item| item.calories)
+    }
+
+    // The parsers make sure that this doesn't overflow.
+    pub fn total(&self) -> u64 {
+        self.calories().sum()
+    }
+}
+
+// The batches read from an input, along with the lines that had to be skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Batches {
+    pub batches: Vec<Batch>,
+    pub invalid: Vec<ParseError>,
+}
+
+// Splits lines into batches of numbers, one line at a time, tolerating the ways that a hand-edited
+// input can vary. Surrounding whitespace is ignored (including the "\r" of a "\r\n" line ending),
+// so that any number of blank lines (or lines of spaces) end a batch. Lines that aren't numbers,
+// or that would make the total of their batch overflow, are skipped and reported.
+#[derive(Debug, Default)]
+pub struct BatchReader {
+    current: Vec<Food>,
+    total: u64,
+    // The number of lines read so far.
+    lines: usize,
+}
+
+impl BatchReader {
+    pub fn new() -> BatchReader {
+        BatchReader::default()
+    }
+
+    // Read the next line. Returns the batch that it ends, if any, or an error if the line had to
+    // be skipped.
+    pub fn push(&mut self, line: &str) -> Result<Option<Batch>, ParseError> {
+        self.lines += 1;
+        let text = line.trim();
+        if text.is_empty() {
+            return Ok(self.finish());
+        }
+        let calories: u64 =
+            parse_field(line, text, "a number of calories").map_err(|e| e.at_line(self.lines))?;
+        self.total = self.total.checked_add(calories).ok_or_else(|| {
+            ParseError::new(
+                line,
+                text,
+                "expected the total of the elf to fit in 64 bits",
+            )
+            .at_line(self.lines)
+        })?;
+        self.current.push(Food {
+            line: self.lines,
+            calories,
+        });
+        Ok(None)
+    }
+
+    // End the batch being read, at the end of the input. Returns it if it has any items.
+    pub fn finish(&mut self) -> Option<Batch> {
+        self.total = 0;
+        if self.current.is_empty() {
+            None
+        } else {
+            Some(Batch {
+                items: std::mem::take(&mut self.current),
+            })
+        }
+    }
+
+    // The items read so far of the batch that hasn't ended yet.
+    pub fn current(&self) -> &[Food] {
+        &self.current
+    }
+}
+
+// Split the input into batches of numbers with a `BatchReader`.
+pub fn read_batches(input: &str) -> Batches {
+    let mut reader = BatchReader::new();
+    let mut batches = Vec::new();
+    let mut invalid = Vec::new();
+    for line in input.lines() {
+        match reader.push(line) {
+            Ok(batch) => batches.extend(batch),
+            Err(e) => invalid.push(e),
+        }
+    }
+    batches.extend(reader.finish());
+    Batches { batches, invalid }
+}
+
+// Split the input into batches of numbers as `read_batches` does, but fail on the first invalid
+// line.
+pub fn parse_batches(input: &str) -> Result<Vec<Batch>, ParseError> {
+    let Batches {
+        batches,
+        mut invalid,
+    } = read_batches(input);
+    if invalid.is_empty() {
+        Ok(batches)
+    } else {
+        Err(invalid.swap_remove(0))
+    }
+}
+
+// An elf in the ranking by calories carried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranked {
+    // The position of the elf in the input (0-based).
+    pub index: usize,
+    pub lines: Range<usize>,
+    pub total: u64,
+}
+
+impl fmt::Display for Ranked {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "elf {} (lines {}-{}): {} calories",
+            self.index + 1,
+            self.lines.start,
+            self.lines.end.saturating_sub(1),
+            self.total
+        )
+    }
+}
+
+// An elf's place in the ranking: more calories first, then earlier in the input first.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Rank(Reverse<u64>, usize);
+
+// The `n` elves carrying the most calories among those inserted so far.
+//
+// The candidates are kept in a max-heap ordered by rank, so that the lowest ranked of them is at
+// the top, ready to be replaced by a better elf. This takes O(log N) per replacement, and the heap
+// never holds more than N elves, however many are inserted.
+#[derive(Debug, Clone)]
+pub struct TopN {
+    n: usize,
+    // Each elf's rank, and the start and end of its lines.
+    heap: BinaryHeap<(Rank, usize, usize)>,
+}
+
+impl TopN {
+    pub fn new(n: usize) -> TopN {
+        TopN {
+            n,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    // Consider the elf at `index` in the input, carrying `batch`. Returns whether it made it into
+    // the top N.
+    pub fn insert(&mut self, index: usize, batch: &Batch) -> bool {
+        let lines = batch.lines();
+        let entry = (Rank(Reverse(batch.total()), index), lines.start, lines.end);
+        if self.heap.len() < self.n {
+            self.heap.push(entry);
+            true
+        } else if self.heap.peek().is_some_and(|worst| entry < *worst) {
+            self.heap.pop();
+            self.heap.push(entry);
+            true
+        } else {
+            false
+        }
+    }
+
+    // The place (0-based) in the ranking of the elf at `index`, carrying `total` calories: the
+    // number of elves ranked ahead of it, counted without sorting them.
+    pub fn place(&self, index: usize, total: u64) -> usize {
+        let rank = Rank(Reverse(total), index);
+        self.heap
+            .iter()
+            .filter(|(other, _, _)| *other < rank)
+            .count()
+    }
+
+    // The elves in the top N, most first. Elves carrying the same number of calories are ranked in
+    // the order they were inserted.
+    pub fn ranking(&self) -> Vec<Ranked> {
+        self.clone().into_ranking()
+    }
+
+    pub fn into_ranking(self) -> Vec<Ranked> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(Rank(Reverse(total), index), start, end)| Ranked {
+                index,
+                lines: start..end,
+                total,
+            })
+            .collect()
+    }
+}
+
+// The `top_n` elves carrying the most calories, most first, with ties in input order.
+pub fn top_elves(batches: &[Batch], top_n: usize) -> Vec<Ranked> {
+    let mut top = TopN::new(top_n);
+    for (index, batch) in batches.iter().enumerate() {
+        top.insert(index, batch);
+    }
+    top.into_ranking()
+}
+
+// The calories carried by the `top_n` elves carrying the most.
+pub fn top_calories(batches: &[Batch], top_n: usize) -> u64 {
+    top_elves(batches, top_n).iter().map(|elf| elf.total).sum()
+}
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    const DAY: u32 = 1;
+
+    type Input = Vec<Batch>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_batches(input)
+    }
+
+    // Part 1: the calories carried by the elf with the most calories.
+    fn part1(batches: &Self::Input) -> u64 {
+        top_calories(batches, 1)
+    }
+
+    // Part 2: the calories carried by the top three elves.
+    fn part2(batches: &Self::Input) -> u64 {
+        top_calories(batches, 3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Write batches in the puzzle format: one number per line, and an empty line between batches.
+    fn render(batches: &[Vec<u64>]) -> String {
+        batches
+            .iter()
+            .map(|batch| batch.iter().map(|n| format!("{}\n", n)).collect())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn batches() -> impl Strategy<Value = Vec<Vec<u64>>> {
+        prop::collection::vec(prop::collection::vec(0..1u64 << 40, 1..10), 1..20)
+    }
+
+    fn calories(batches: &[Batch]) -> Vec<Vec<u64>> {
+        batches
+            .iter()
+            .map(|batch| batch.calories().collect())
+            .collect()
+    }
+
+    const TEST_INPUT: &str = include_str!("../data/test.txt");
+
+    #[test]
+    fn test_parse_batches() {
+        let batches = parse_batches(TEST_INPUT).unwrap();
+        assert_eq!(batches.len(), 5);
+        assert_eq!(batches[2].lines(), 7..9);
+        assert_eq!(calories(&batches)[2], vec![5000, 6000]);
+        assert_eq!(batches[4].lines(), 14..15);
+    }
+
+    #[test]
+    fn test_top_elves() {
+        let batches = parse_batches(TEST_INPUT).unwrap();
+        let top = top_elves(&batches, 3);
+        assert_eq!(
+            top,
+            vec![
+                Ranked {
+                    index: 3,
+                    lines: 10..13,
+                    total: 24000
+                },
+                Ranked {
+                    index: 2,
+                    lines: 7..9,
+                    total: 11000
+                },
+                Ranked {
+                    index: 4,
+                    lines: 14..15,
+                    total: 10000
+                },
+            ]
+        );
+        assert_eq!(top[0].to_string(), "elf 4 (lines 10-12): 24000 calories");
+        assert_eq!(top_elves(&batches, 10).len(), 5);
+        assert_eq!(top_calories(&batches, 3), 45000);
+    }
+
+    #[test]
+    fn test_ties() {
+        // Elves carrying the same number of calories keep their order from the input.
+        let batches = parse_batches("5\n\n7\n\n2\n3\n\n7\n").unwrap();
+        let indices: Vec<usize> = top_elves(&batches, 4).iter().map(|elf| elf.index).collect();
+        assert_eq!(indices, vec![1, 3, 0, 2]);
+        let indices: Vec<usize> = top_elves(&batches, 2).iter().map(|elf| elf.index).collect();
+        assert_eq!(indices, vec![1, 3]);
+        assert_eq!(top_elves(&batches, 0), vec![]);
+    }
+
+    #[test]
+    fn test_read_batches() {
+        // Windows line endings, spaces, runs of blank lines and stray text.
+        let input = "\r\n1000\r\n 2000 \r\n  \r\n\r\n\t\n3000\nthree\n-4\n\n\n";
+        let Batches { batches, invalid } = read_batches(input);
+        assert_eq!(calories(&batches), vec![vec![1000, 2000], vec![3000]]);
+        assert_eq!(batches[0].lines(), 2..4);
+        assert_eq!(batches[1].lines(), 7..8);
+        let invalid: Vec<(usize, usize, &str)> = invalid
+            .iter()
+            .map(|e| (e.line, e.column, e.text.as_str()))
+            .collect();
+        assert_eq!(invalid, vec![(8, 1, "three"), (9, 1, "-4")]);
+        assert_eq!(parse_batches(input).unwrap_err().line, 8);
+
+        // Totals are 64 bits wide, and may not overflow.
+        let input = format!("{}\n1\n\n{}\n{}\n", u32::MAX, u64::MAX, u64::MAX);
+        let Batches { batches, invalid } = read_batches(&input);
+        assert_eq!(batches[0].total(), u32::MAX as u64 + 1);
+        assert_eq!(batches[1].total(), u64::MAX);
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].line, 5);
+        assert_eq!(read_batches("").batches, vec![]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(batches in batches()) {
+            let parsed = parse_batches(&render(&batches)).unwrap();
+            prop_assert_eq!(calories(&parsed), batches);
+        }
+
+        #[test]
+        fn test_tolerant(
+            batches in batches(),
+            padding in prop::collection::vec(("[ \t]{0,2}", "[ \t]{0,2}", any::<bool>()), 200),
+            separators in prop::collection::vec("([ \t]*\r?\n){1,3}", 20),
+        ) {
+            // Pad the numbers, use any line endings, and separate the batches by any blank lines.
+            let mut padding = padding.into_iter();
+            let text: Vec<String> = batches
+                .iter()
+                .map(|batch| {
+                    batch
+                        .iter()
+                        .map(|n| {
+                            let (before, after, crlf) = padding.next().unwrap();
+                            format!("{}{}{}{}", before, n, after, if crlf { "\r\n" } else { "\n" })
+                        })
+                        .collect::<String>()
+                })
+                .collect();
+            let mut input = String::new();
+            for (batch, separator) in text.iter().zip(&separators) {
+                input.push_str(batch);
+                input.push_str(separator);
+            }
+            prop_assert_eq!(calories(&parse_batches(&input).unwrap()), batches);
+        }
+
+        #[test]
+        fn test_invalid_line(
+            batches in batches(),
+            line in any::<prop::sample::Index>(),
+            junk in "[a-z.]{1,3}",
+        ) {
+            let text = render(&batches);
+            let mut lines: Vec<&str> = text.lines().collect();
+            let i = line.index(lines.len());
+            let corrupted = format!("{}{}", lines[i], junk);
+            lines[i] = &corrupted;
+            let e = parse_batches(&lines.join("\n")).unwrap_err();
+            prop_assert_eq!(e.line, i + 1);
+        }
+
+        #[test]
+        fn test_top_elves_sorted(totals in prop::collection::vec(0..10u64, 0..50), top_n in 0..60usize) {
+            // Compare with sorting all of the elves.
+            let batches: Vec<Batch> = totals
+                .iter()
+                .enumerate()
+                .map(|(i, &calories)| Batch { items: vec![Food { line: 2 * i + 1, calories }] })
+                .collect();
+            let mut expected: Vec<(usize, u64)> = totals.iter().copied().enumerate().collect();
+            expected.sort_by_key(|&(index, total)| (Reverse(total), index));
+            expected.truncate(top_n);
+            let top: Vec<(usize, u64)> =
+                top_elves(&batches, top_n).iter().map(|elf| (elf.index, elf.total)).collect();
+            prop_assert_eq!(top, expected);
+        }
+
+        #[test]
+        fn test_no_panic(input in "[0-9a-z \t\r\n-]{0,100}") {
+            let _ = parse_batches(&input);
+        }
+    }
+}
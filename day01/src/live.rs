@@ -0,0 +1,159 @@
+// Keeping track of the top elves while the input is still being written, for `day01 live`. Lines
+// are fed in one at a time, and the leaderboard is updated whenever an elf's batch ends. Only the
+// top N elves and the batch being read are kept, so the input can go on for ever.
+
+use crate::{Batch, BatchReader, Food, ParseError, Ranked, TopN};
+use std::fmt;
+
+// The end of an elf's batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Update {
+    pub elf: Ranked,
+    // The elf's place in the top N (1-based), if it made it.
+    pub place: Option<usize>,
+}
+
+impl fmt::Display for Update {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.place {
+            Some(place) => write!(f, "{}, in place {}", self.elf, place),
+            None => write!(f, "{}", self.elf),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Leaderboard {
+    reader: BatchReader,
+    top: TopN,
+    // The number of batches that have ended.
+    elves: usize,
+}
+
+impl Leaderboard {
+    pub fn new(top_n: usize) -> Leaderboard {
+        Leaderboard {
+            reader: BatchReader::new(),
+            top: TopN::new(top_n),
+            elves: 0,
+        }
+    }
+
+    // Read the next line. Returns the update if it ends a batch, or an error if the line had to
+    // be skipped.
+    pub fn push(&mut self, line: &str) -> Result<Option<Update>, ParseError> {
+        let batch = self.reader.push(line)?;
+        Ok(batch.map(|batch| self.add(batch)))
+    }
+
+    // End the batch being read, if any, at the end of the input.
+    pub fn finish(&mut self) -> Option<Update> {
+        self.reader.finish().map(|batch| self.add(batch))
+    }
+
+    fn add(&mut self, batch: Batch) -> Update {
+        let index = self.elves;
+        self.elves += 1;
+        let total = batch.total();
+        let place = self
+            .top
+            .insert(index, &batch)
+            .then(|| self.top.place(index, total) + 1);
+        Update {
+            elf: Ranked {
+                index,
+                lines: batch.lines(),
+                total,
+            },
+            place,
+        }
+    }
+
+    // The current top N, most first.
+    pub fn top(&self) -> Vec<Ranked> {
+        self.top.ranking()
+    }
+
+    // The number of elves whose batches have ended.
+    pub fn elves(&self) -> usize {
+        self.elves
+    }
+
+    // The items of the batch that is still being read.
+    pub fn pending(&self) -> &[Food] {
+        self.reader.current()
+    }
+}
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, elf) in self.top().iter().enumerate() {
+            writeln!(f, "{:>4}. {}", i + 1, elf)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../data/test.txt");
+
+    #[test]
+    fn test_leaderboard() {
+        let mut leaderboard = Leaderboard::new(2);
+        let mut updates = Vec::new();
+        for line in TEST_INPUT.lines() {
+            updates.extend(leaderboard.push(line).unwrap());
+        }
+        // The last batch hasn't ended yet.
+        assert_eq!(updates.len(), 4);
+        assert_eq!(leaderboard.elves(), 4);
+        assert_eq!(leaderboard.pending().len(), 1);
+        let places: Vec<Option<usize>> = updates.iter().map(|u| u.place).collect();
+        assert_eq!(places, vec![Some(1), Some(2), Some(1), Some(1)]);
+        assert_eq!(
+            updates[3].to_string(),
+            "elf 4 (lines 10-12): 24000 calories, in place 1"
+        );
+
+        let last = leaderboard.finish().unwrap();
+        assert_eq!(last.place, None);
+        assert_eq!(leaderboard.finish(), None);
+        let top: Vec<u64> = leaderboard.top().iter().map(|elf| elf.total).collect();
+        assert_eq!(top, vec![24000, 11000]);
+        assert_eq!(
+            leaderboard.to_string(),
+            "   1. elf 4 (lines 10-12): 24000 calories\n   2. elf 3 (lines 7-8): 11000 calories\n"
+        );
+    }
+
+    #[test]
+    fn test_ties() {
+        // Elves carrying the same go in input order, so a later one can't displace an earlier.
+        let mut leaderboard = Leaderboard::new(3);
+        let places: Vec<Option<usize>> = ["5", "5", "5", "6", "5", "7"]
+            .iter()
+            .map(|&calories| {
+                assert_eq!(leaderboard.push(calories), Ok(None));
+                leaderboard.push("").unwrap().unwrap().place
+            })
+            .collect();
+        assert_eq!(
+            places,
+            vec![Some(1), Some(2), Some(3), Some(1), None, Some(1)]
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut leaderboard = Leaderboard::new(3);
+        assert_eq!(leaderboard.push("100"), Ok(None));
+        assert_eq!(leaderboard.push("lots").unwrap_err().line, 2);
+        assert_eq!(leaderboard.push("200"), Ok(None));
+        let update = leaderboard.push("").unwrap().unwrap();
+        assert_eq!(update.elf.total, 300);
+        assert_eq!(update.elf.lines, 1..4);
+    }
+}
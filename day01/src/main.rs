@@ -1,47 +1,278 @@
-// Advent of Code 2022 - Day 1
-// Read batches of numbers from the input file, and find the batch with the largest sum.
-
-use std::fs::File;
-use std::io::{BufRead, BufReader, Seek};
-
-fn argmax_calories<R: BufRead>(reader: &mut R, top_n: usize) -> i32 {
-    let mut max = vec![0; top_n];
-    let mut min_idx = 0;
-    let mut min = 0;
-    let mut sum = 0;
-
-    reader.lines().for_each(|line| {
-        // If the line is empty, we've reached the end of a batch. Check whether the
-        // sum is larger than the previous maximum, save it if it is, and reset the sum.
-        if line.as_ref().unwrap().is_empty() {
-            if sum > min {
-                max[min_idx] = sum;
-                min_idx = max.iter().enumerate().min_by_key(|(_, &v)| v).unwrap().0;
-                min = max[min_idx];
+// Besides solving the puzzle, the elves' food can be analysed with a subcommand:
+//
+//     day01 stats [--test] [--bins N] [INPUT | -]
+//     day01 balance [--test] [--exact | --greedy] [INPUT | -]
+//     day01 live [--test] [--top N] [--follow] [INPUT | -]
+//
+// `stats` describes the calories carried by all of the elves, and `balance` plans how to
+// redistribute the items so that no elf carries more than necessary. `live` reads the input as it
+// is written, and prints the leaderboard each time an elf's batch ends; with `--follow` it keeps
+// waiting for more at the end of the file, like `tail -f`.
+
+use aoc_common::cli::{self, Source};
+use day01::balance::{self, Method};
+use day01::live::{Leaderboard, Update};
+use day01::stats::Summariser;
+use day01::{Batch, BatchReader, Day01};
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+// How often to check for more input at the end of a file with `--follow`.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+const USAGE: &str = "Usage: day01 stats [--test] [--bins N] [INPUT | -]\n       \
+    day01 balance [--test] [--exact | --greedy] [INPUT | -]\n       \
+    day01 live [--test] [--top N] [--follow] [INPUT | -]\n\n\
+    stats prints statistics and a histogram of the calories carried by each elf. balance plans\n\
+    how to move food items between the elves to make the largest load as small as possible.\n\
+    live prints the top N elves (3 by default) each time one of the elves has been read, and\n\
+    with --follow waits for more at the end of INPUT.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Stats { bins: usize },
+    Balance { method: Method },
+    Live { top: usize, follow: bool },
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    command: Command,
+    source: Source,
+}
+
+// Parse the arguments following the name of the subcommand `command`.
+fn parse_args<I: Iterator<Item = String>>(
+    command: &str,
+    args: I,
+    crate_dir: impl AsRef<Path>,
+) -> Result<Options, String> {
+    let mut command = match command {
+        "stats" => Command::Stats { bins: 10 },
+        "balance" => Command::Balance {
+            method: Method::Auto,
+        },
+        "live" => Command::Live {
+            top: 3,
+            follow: false,
+        },
+        _ => return Err(format!("Unknown command: {}", command)),
+    };
+    let source = cli::parse_command(args, crate_dir, |arg, args| {
+        match (arg, &mut command) {
+            ("-b" | "--bins", Command::Stats { bins }) => {
+                let v = args.value(arg)?;
+                *bins = match v.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("Invalid number of bins: {}", v)),
+                };
             }
-            sum = 0;
-        } else {
-            // Otherwise, add the number to the sum.
-            sum += line.unwrap().parse::<i32>().unwrap();
+            ("--exact", Command::Balance { method }) => *method = Method::Exact,
+            ("--greedy", Command::Balance { method }) => *method = Method::Greedy,
+            ("-n" | "--top", Command::Live { top, .. }) => {
+                let v = args.value(arg)?;
+                *top = match v.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("Invalid number of elves: {}", v)),
+                };
+            }
+            ("-F" | "--follow", Command::Live { follow, .. }) => *follow = true,
+            _ => return Ok(false),
         }
-    });
+        Ok(true)
+    })?;
+    Ok(Options { command, source })
+}
+
+// Read the batches from `source` line by line, passing each to `f` as soon as it ends, and
+// carrying on past lines that aren't numbers after warning about them.
+fn for_each_batch(source: &Source, mut f: impl FnMut(Batch)) -> Result<(), String> {
+    let name = source.name();
+    let error = |e: io::Error| format!("could not read {}: {}", name.display(), e);
+    let mut reader = BatchReader::new();
+    for line in source.open().map_err(error)?.lines() {
+        match reader.push(&line.map_err(error)?) {
+            Ok(batch) => batch.into_iter().for_each(&mut f),
+            Err(e) => eprintln!("warning: skipping {}", e.in_file(&name)),
+        }
+    }
+    reader.finish().into_iter().for_each(f);
+    Ok(())
+}
 
-    // If we've reached to end of the file, we still need to check the last batch.
-    if sum > min {
-        max[min_idx] = sum;
+fn run(options: &Options) -> Result<String, String> {
+    let name = options.source.name();
+    match options.command {
+        Command::Stats { bins } => {
+            // Only the total of each elf is kept.
+            let mut summariser = Summariser::new();
+            for_each_batch(&options.source, |batch| summariser.push(&batch))?;
+            let summary = summariser.finish();
+            Ok(format!(
+                "{}\n{}",
+                summary,
+                summary.render_histogram(bins, 50)
+            ))
+        }
+        Command::Balance { method } => {
+            let mut batches = Vec::new();
+            for_each_batch(&options.source, |batch| batches.push(batch))?;
+            balance::plan(&batches, method)
+                .map(|plan| plan.to_string())
+                .map_err(|e| format!("{}: {}", name.display(), e))
+        }
+        // The leaderboard is printed as it changes, rather than reported at the end.
+        Command::Live { top, follow } => {
+            let reader = options
+                .source
+                .open()
+                .map_err(|e| format!("could not read {}: {}", name.display(), e))?;
+            live(top, follow, &name, reader, &mut io::stdout())?;
+            Ok(String::new())
+        }
     }
+}
 
-    println!("Final: {:?}", max);
-    max.iter().sum()
+// Read `reader` line by line, and write the leaderboard of the `top` elves to `out` whenever it
+// changes. `name` is the name of the input in diagnostics.
+fn live<R: BufRead, W: Write>(
+    top: usize,
+    follow: bool,
+    name: &Path,
+    mut reader: R,
+    out: &mut W,
+) -> Result<Leaderboard, String> {
+    let mut leaderboard = Leaderboard::new(top);
+    let mut show = |leaderboard: &Leaderboard, update: Update| {
+        writeln!(out, "{}\n{}", update, leaderboard).and_then(|_| out.flush())
+    };
+    let mut line = String::new();
+    loop {
+        let n = reader
+            .read_line(&mut line)
+            .map_err(|e| format!("could not read {}: {}", name.display(), e))?;
+        // When following, a line isn't complete until its newline has been written too.
+        if follow && !line.ends_with('\n') {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        } else if n == 0 {
+            break;
+        }
+        match leaderboard.push(&line) {
+            Ok(Some(update)) => show(&leaderboard, update).map_err(|e| e.to_string())?,
+            Ok(None) => {}
+            Err(e) => eprintln!("warning: skipping {}", e.in_file(name)),
+        }
+        line.clear();
+    }
+    if let Some(update) = leaderboard.finish() {
+        show(&leaderboard, update).map_err(|e| e.to_string())?;
+    }
+    Ok(leaderboard)
 }
 
 fn main() {
-    let file = File::open("data/input.txt").unwrap();
-    let mut reader = BufReader::new(file);
-    let result = argmax_calories(&mut reader, 1);
-    println!("Part 1: {:?}", result);
-
-    reader.rewind().unwrap();
-    let result = argmax_calories(&mut reader, 3);
-    println!("Part 2: {:?}", result);
+    let crate_dir = env!("CARGO_MANIFEST_DIR");
+    let mut args = std::env::args().skip(1).peekable();
+    if let Some(command) = args.next_if(|arg| ["stats", "balance", "live"].contains(&arg.as_str()))
+    {
+        let options = parse_args(&command, args, crate_dir).unwrap_or_else(|e| {
+            eprintln!("error: {}\n{}", e, USAGE);
+            process::exit(2);
+        });
+        match run(&options) {
+            Ok(report) => print!("{}", report),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    aoc_common::cli::run::<Day01>(crate_dir, |part, answer| {
+        format!("Part {}: {}", part, answer)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn args(command: &str, s: &str) -> Result<Options, String> {
+        parse_args(
+            command,
+            s.split_whitespace().map(String::from),
+            "/aoc/day01",
+        )
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args("stats", "--test --bins 5"),
+            Ok(Options {
+                command: Command::Stats { bins: 5 },
+                source: Source::File(PathBuf::from("/aoc/day01/data/test.txt")),
+            })
+        );
+        assert_eq!(args("stats", "-").unwrap().source, Source::Stdin);
+        assert!(args("stats", "--bins 0").is_err());
+        assert!(args("stats", "--test foo.txt").is_err());
+        assert!(args("stats", "--greedy").is_err());
+        assert_eq!(
+            args("balance", "--greedy").unwrap().command,
+            Command::Balance {
+                method: Method::Greedy
+            }
+        );
+        assert!(args("balance", "--bins 3").is_err());
+        assert_eq!(
+            args("live", "-n 5 --follow -").unwrap(),
+            Options {
+                command: Command::Live {
+                    top: 5,
+                    follow: true
+                },
+                source: Source::Stdin,
+            }
+        );
+        assert!(args("live", "--top 0").is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let options = |command: &str, s: &str| {
+            parse_args(
+                command,
+                s.split_whitespace().map(String::from),
+                env!("CARGO_MANIFEST_DIR"),
+            )
+            .unwrap()
+        };
+        let report = run(&options("stats", "--test --bins 4")).unwrap();
+        assert!(report.starts_with("elves: 5\n"));
+        assert!(report.ends_with("19003-24000 | ######################### 1\n"));
+        let report = run(&options("balance", "--test")).unwrap();
+        assert!(report.starts_with("largest load: 11000 (was 24000), which is optimal\n"));
+    }
+
+    #[test]
+    fn test_live() {
+        let input = "1000\r\n2000\r\n\r\n4000\n\n\n\n5000\nx\n6000\n\n7000\n";
+        let mut out = Vec::new();
+        let leaderboard = live(2, false, Path::new("<stdin>"), input.as_bytes(), &mut out).unwrap();
+        assert_eq!(leaderboard.elves(), 4);
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("calories, in place").count(), 4);
+        assert!(out.ends_with(
+            "elf 4 (lines 12-12): 7000 calories, in place 2\n   \
+             1. elf 3 (lines 8-10): 11000 calories\n   \
+             2. elf 4 (lines 12-12): 7000 calories\n\n"
+        ));
+    }
 }
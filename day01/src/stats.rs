@@ -0,0 +1,280 @@
+// Descriptive statistics over the calories carried by all of the elves, for `day01 stats`.
+//
+// The batches are summarised in a single pass by a `Summariser`: the count, mean and variance are
+// accumulated as they go (with Welford's method, which doesn't lose precision on large totals),
+// and only the total of each elf is kept for the percentiles and the histogram, not the items
+// themselves.
+
+use crate::Batch;
+use std::fmt;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Summary {
+    pub elves: usize,
+    pub items: usize,
+    pub min_items: usize,
+    pub max_items: usize,
+    pub mean: f64,
+    pub std_dev: f64,
+    // The total carried by each elf, in increasing order.
+    totals: Vec<u64>,
+}
+
+// A bin of the histogram: the number of elves whose totals are in `start..=end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bin {
+    pub start: u64,
+    pub end: u64,
+    pub count: usize,
+}
+
+// Summarises the batches one at a time, as they are read.
+#[derive(Debug, Clone, Default)]
+pub struct Summariser {
+    summary: Summary,
+    // The sum of the squared differences from the mean.
+    m2: f64,
+}
+
+impl Summariser {
+    pub fn new() -> Summariser {
+        Summariser::default()
+    }
+
+    pub fn push(&mut self, batch: &Batch) {
+        let summary = &mut self.summary;
+        let total = batch.total();
+        let items = batch.items.len();
+        summary.elves += 1;
+        summary.items += items;
+        summary.min_items = match summary.elves {
+            1 => items,
+            _ => summary.min_items.min(items),
+        };
+        summary.max_items = summary.max_items.max(items);
+        let delta = total as f64 - summary.mean;
+        summary.mean += delta / summary.elves as f64;
+        self.m2 += delta * (total as f64 - summary.mean);
+        summary.totals.push(total);
+    }
+
+    pub fn finish(self) -> Summary {
+        let mut summary = self.summary;
+        if summary.elves > 0 {
+            summary.std_dev = (self.m2 / summary.elves as f64).sqrt();
+        }
+        summary.totals.sort_unstable();
+        summary
+    }
+}
+
+impl Summary {
+    pub fn new<'a>(batches: impl IntoIterator<Item = &'a Batch>) -> Summary {
+        let mut summariser = Summariser::new();
+        for batch in batches {
+            summariser.push(batch);
+        }
+        summariser.finish()
+    }
+
+    // The total of all of the elves, which can take more than 64 bits.
+    pub fn total(&self) -> u128 {
+        self.totals.iter().map(|&total| total as u128).sum()
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.totals.first().copied()
+    }
+
+    pub fn max(&self) -> Option<u64> {
+        self.totals.last().copied()
+    }
+
+    // The `p`th percentile (0 to 100) of the totals, interpolating linearly between the closest
+    // ranks.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if self.totals.is_empty() {
+            return None;
+        }
+        let rank = p.clamp(0.0, 100.0) / 100.0 * (self.totals.len() - 1) as f64;
+        let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+        let (low, high) = (self.totals[below] as f64, self.totals[above] as f64);
+        Some(low + (high - low) * (rank - below as f64))
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    // Divide the range of the totals into `bins` bins of equal width, and count the elves in each.
+    // There are fewer bins if the range is narrower than that.
+    pub fn histogram(&self, bins: usize) -> Vec<Bin> {
+        let (Some(min), Some(max)) = (self.min(), self.max()) else {
+            return Vec::new();
+        };
+        // The range can be as wide as 2^64, and so can a single bin.
+        let range = (max - min) as u128 + 1;
+        let width = range.div_ceil(bins.max(1) as u128);
+        let mut histogram: Vec<Bin> = (0..range.div_ceil(width))
+            .map(|i| {
+                let start = min as u128 + i * width;
+                Bin {
+                    start: start as u64,
+                    end: (start + width - 1).min(max as u128) as u64,
+                    count: 0,
+                }
+            })
+            .collect();
+        for &total in &self.totals {
+            histogram[((total - min) as u128 / width) as usize].count += 1;
+        }
+        histogram
+    }
+
+    // Draw the histogram with bars of at most `width` characters.
+    pub fn render_histogram(&self, bins: usize, width: usize) -> String {
+        let histogram = self.histogram(bins);
+        let largest = histogram.iter().map(|bin| bin.count).max().unwrap_or(0);
+        let digits = |n: u64| n.to_string().len();
+        let label_width = histogram
+            .iter()
+            .map(|bin| digits(bin.start).max(digits(bin.end)))
+            .max()
+            .unwrap_or(0);
+        histogram
+            .iter()
+            .map(|bin| {
+                // Any elves at all get at least part of a bar.
+                let bar = (bin.count * width).div_ceil(largest.max(1));
+                format!(
+                    "{:>w$}-{:>w$} | {} {}\n",
+                    bin.start,
+                    bin.end,
+                    "#".repeat(bar),
+                    bin.count,
+                    w = label_width
+                )
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "elves: {}", self.elves)?;
+        if self.elves == 0 {
+            return Ok(());
+        }
+        writeln!(
+            f,
+            "items: {} ({} to {} per elf, {:.1} on average)",
+            self.items,
+            self.min_items,
+            self.max_items,
+            self.items as f64 / self.elves as f64
+        )?;
+        writeln!(
+            f,
+            "calories: {} in total, {:.1} on average, standard deviation {:.1}",
+            self.total(),
+            self.mean,
+            self.std_dev
+        )?;
+        write!(f, "min {}", self.min().unwrap())?;
+        for p in [10, 25, 50, 75, 90, 99] {
+            write!(f, ", p{} {:.1}", p, self.percentile(p as f64).unwrap())?;
+        }
+        writeln!(f, ", max {}", self.max().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_batches;
+
+    const TEST_INPUT: &str = include_str!("../data/test.txt");
+
+    #[test]
+    fn test_summary() {
+        let batches = parse_batches(TEST_INPUT).unwrap();
+        let summary = Summary::new(&batches);
+        assert_eq!(summary.elves, 5);
+        assert_eq!(
+            (summary.items, summary.min_items, summary.max_items),
+            (10, 1, 3)
+        );
+        assert_eq!(summary.total(), 55000);
+        assert_eq!(summary.mean, 11000.0);
+        // The totals are 6000, 4000, 11000, 24000 and 10000.
+        let variance = [5000.0f64, 7000.0, 0.0, 13000.0, 1000.0]
+            .iter()
+            .map(|d| d * d)
+            .sum::<f64>()
+            / 5.0;
+        assert!((summary.std_dev - variance.sqrt()).abs() < 1e-9);
+        assert_eq!(summary.median(), Some(10000.0));
+        assert_eq!(summary.percentile(0.0), Some(4000.0));
+        assert_eq!(summary.percentile(100.0), Some(24000.0));
+        assert_eq!(summary.percentile(75.0), Some(11000.0));
+        assert_eq!(summary.percentile(12.5), Some(5000.0));
+        assert!(summary
+            .to_string()
+            .contains("min 4000, p10 4800.0, p25 6000.0, p50 10000.0"));
+    }
+
+    #[test]
+    fn test_histogram() {
+        let batches = parse_batches(TEST_INPUT).unwrap();
+        let summary = Summary::new(&batches);
+        let counts: Vec<usize> = summary.histogram(4).iter().map(|bin| bin.count).collect();
+        assert_eq!(counts, vec![2, 2, 0, 1]);
+        assert_eq!(
+            summary.histogram(4)[0],
+            Bin {
+                start: 4000,
+                end: 9000,
+                count: 2
+            }
+        );
+        let expected = [
+            " 4000- 9000 | ###### 2",
+            " 9001-14001 | ###### 2",
+            "14002-19002 |  0",
+            "19003-24000 | ### 1",
+        ];
+        assert_eq!(
+            summary.render_histogram(4, 6),
+            expected.map(|line| format!("{}\n", line)).concat()
+        );
+        // The bins are never narrower than one calorie.
+        let batches = parse_batches("1\n\n2\n\n2\n").unwrap();
+        assert_eq!(Summary::new(&batches).histogram(10).len(), 2);
+    }
+
+    #[test]
+    fn test_wide() {
+        let input = format!("0\n\n{}\n\n{}\n", u64::MAX, u64::MAX);
+        let batches = parse_batches(&input).unwrap();
+        let summary = Summary::new(&batches);
+        assert_eq!(summary.total(), 2 * u64::MAX as u128);
+        let counts: Vec<usize> = summary.histogram(2).iter().map(|bin| bin.count).collect();
+        assert_eq!(counts, vec![1, 2]);
+        assert_eq!(
+            summary.histogram(1),
+            vec![Bin {
+                start: 0,
+                end: u64::MAX,
+                count: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let summary = Summary::new(&[]);
+        assert_eq!(summary.median(), None);
+        assert_eq!(summary.histogram(10), vec![]);
+        assert_eq!(summary.to_string(), "elves: 0\n");
+    }
+}
@@ -0,0 +1,215 @@
+// Every way of reading the second column of the strategy guide, for `day02 analyse`. Parts 1 and 2
+// are two guesses at what X, Y and Z mean: the shapes rock, paper and scissors, or the outcomes
+// lose, draw and win. Here each of the six orders of the shapes and of the outcomes is scored.
+//
+// The score of a reading only depends on how many times each (opponent, column) pair occurs, so
+// the guide is counted once, and each reading is scored from the nine counts.
+
+use crate::{score1, score2, Column, Outcome, Round, Shape};
+use std::fmt;
+
+// What X, Y and Z stand for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mapping {
+    Shapes([Shape; 3]),
+    Outcomes([Outcome; 3]),
+}
+
+impl Mapping {
+    // The readings of the puzzle's two parts.
+    pub const PART1: Mapping = Mapping::Shapes(Shape::ALL);
+    pub const PART2: Mapping = Mapping::Outcomes(Outcome::ALL);
+
+    // All twelve readings, the shapes first.
+    pub fn all() -> Vec<Mapping> {
+        let shapes = permutations(Shape::ALL).map(Mapping::Shapes);
+        let outcomes = permutations(Outcome::ALL).map(Mapping::Outcomes);
+        shapes.into_iter().chain(outcomes).collect()
+    }
+
+    pub fn score(self, opponent: Shape, column: Column) -> u32 {
+        match self {
+            Mapping::Shapes(shapes) => score1(opponent, shapes[column as usize]),
+            Mapping::Outcomes(outcomes) => score2(opponent, outcomes[column as usize]),
+        }
+    }
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let meanings = match self {
+            Mapping::Shapes(shapes) => shapes.map(|shape| shape.to_string()),
+            Mapping::Outcomes(outcomes) => outcomes.map(|outcome| outcome.to_string()),
+        };
+        for (column, meaning) in Column::ALL.iter().zip(meanings) {
+            if *column != Column::X {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?}={}", column, meaning)?;
+        }
+        Ok(())
+    }
+}
+
+// The six orders of three values.
+fn permutations<T: Copy>([a, b, c]: [T; 3]) -> [[T; 3]; 6] {
+    [
+        [a, b, c],
+        [a, c, b],
+        [b, a, c],
+        [b, c, a],
+        [c, a, b],
+        [c, b, a],
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interpretation {
+    pub mapping: Mapping,
+    pub score: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    // From the highest score to the lowest. Readings with the same score are in the order of
+    // `Mapping::all`.
+    pub interpretations: Vec<Interpretation>,
+}
+
+impl Analysis {
+    pub fn new(rounds: &[Round]) -> Analysis {
+        let mut counts = [[0u64; 3]; 3];
+        for round in rounds {
+            counts[round.opponent as usize][round.column as usize] += 1;
+        }
+        let mut interpretations: Vec<Interpretation> = Mapping::all()
+            .into_iter()
+            .map(|mapping| {
+                let mut score = 0;
+                for opponent in Shape::ALL {
+                    for column in Column::ALL {
+                        let count = counts[opponent as usize][column as usize];
+                        score += count * mapping.score(opponent, column) as u64;
+                    }
+                }
+                Interpretation { mapping, score }
+            })
+            .collect();
+        interpretations.sort_by_key(|interpretation| std::cmp::Reverse(interpretation.score));
+        Analysis { interpretations }
+    }
+
+    pub fn best(&self) -> Interpretation {
+        self.interpretations[0]
+    }
+
+    pub fn worst(&self) -> Interpretation {
+        self.interpretations[self.interpretations.len() - 1]
+    }
+
+    pub fn score(&self, mapping: Mapping) -> u64 {
+        self.interpretations
+            .iter()
+            .find(|interpretation| interpretation.mapping == mapping)
+            .unwrap()
+            .score
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (best, worst) = (self.best().score, self.worst().score);
+        let width = best.to_string().len();
+        for interpretation in &self.interpretations {
+            let mut notes = Vec::new();
+            if interpretation.score == best {
+                notes.push("best");
+            }
+            if interpretation.score == worst {
+                notes.push("worst");
+            }
+            if interpretation.mapping == Mapping::PART1 {
+                notes.push("part 1");
+            } else if interpretation.mapping == Mapping::PART2 {
+                notes.push("part 2");
+            }
+            let line = format!(
+                "{:>w$}  {}",
+                interpretation.score,
+                interpretation.mapping,
+                w = width
+            );
+            if notes.is_empty() {
+                writeln!(f, "{}", line)?;
+            } else {
+                writeln!(f, "{:<40} <- {}", line, notes.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+    use proptest::prelude::*;
+
+    const TEST_INPUT: &str = include_str!("../data/test.txt");
+
+    #[test]
+    fn test_analysis() {
+        let rounds = parse_input(TEST_INPUT).unwrap();
+        let analysis = Analysis::new(&rounds);
+        assert_eq!(analysis.interpretations.len(), 12);
+        assert_eq!(analysis.score(Mapping::PART1), 15);
+        assert_eq!(analysis.score(Mapping::PART2), 12);
+        // Winning every round: paper (Y) against rock, scissors (X) against paper and rock (Z)
+        // against scissors.
+        assert_eq!(
+            analysis.best(),
+            Interpretation {
+                mapping: Mapping::Shapes([Shape::Scissors, Shape::Paper, Shape::Rock]),
+                score: 2 + 3 + 1 + 18,
+            }
+        );
+        assert_eq!(
+            analysis.worst(),
+            Interpretation {
+                mapping: Mapping::Shapes([Shape::Rock, Shape::Scissors, Shape::Paper]),
+                score: 3 + 1 + 2,
+            }
+        );
+        let report = analysis.to_string();
+        assert!(report.starts_with("24  X=scissors, Y=paper, Z=rock"));
+        assert!(report.contains("15  X=rock, Y=paper, Z=scissors"));
+        assert!(report.contains("<- part 2\n"));
+        assert!(report.ends_with("<- worst\n"));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Mapping::PART2.to_string(), "X=lose, Y=draw, Z=win");
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_rounds(
+            rounds in prop::collection::vec((0..3usize, 0..3usize), 0..50),
+        ) {
+            let rounds: Vec<Round> = rounds
+                .into_iter()
+                .map(|(a, b)| Round { opponent: Shape::ALL[a], column: Column::ALL[b] })
+                .collect();
+            let analysis = Analysis::new(&rounds);
+            for interpretation in &analysis.interpretations {
+                let score: u64 = rounds
+                    .iter()
+                    .map(|round| interpretation.mapping.score(round.opponent, round.column) as u64)
+                    .sum();
+                prop_assert_eq!(interpretation.score, score);
+            }
+            prop_assert!(analysis.interpretations.windows(2).all(|w| w[0].score >= w[1].score));
+        }
+    }
+}
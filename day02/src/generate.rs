@@ -0,0 +1,34 @@
+// Synthetic inputs: a strategy guide of `size` rounds.
+
+use crate::Day02;
+use aoc_common::{Generate, Rng};
+
+impl Generate for Day02 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut input = String::with_capacity(size * 4);
+        for _ in 0..size {
+            input.push(*rng.choose(&['A', 'B', 'C']));
+            input.push(' ');
+            input.push(*rng.choose(&['X', 'Y', 'Z']));
+            input.push('\n');
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_generate() {
+        let input = Day02::generate(&mut Rng::new(1), 100);
+        assert_eq!(input, Day02::generate(&mut Rng::new(1), 100));
+        let rounds = Day02::parse(&input).unwrap();
+        assert_eq!(rounds.len(), 100);
+        // Every round scores at least 1 and at most 9 points.
+        assert!((100..=900).contains(&Day02::part1(&rounds)));
+        assert!((100..=900).contains(&Day02::part2(&rounds)));
+    }
+}
@@ -0,0 +1,329 @@
+// Advent of Code 2022 - Day 2
+// Calculate the score of a Rock Paper Scissors game
+
+pub mod analysis;
+mod generate;
+pub mod optimise;
+pub mod rules;
+pub mod tournament;
+
+use aoc_common::error::next_field;
+use aoc_common::{ParseError, Solver};
+use std::fmt;
+
+// The shapes, in the order of their scores. Each shape beats the one before it, and Rock beats
+// Scissors to close the cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    pub const ALL: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissors];
+
+    // The shape that this one beats.
+    pub fn beats(self) -> Shape {
+        Shape::ALL[(self as usize + 2) % 3]
+    }
+
+    // The shape that beats this one.
+    pub fn beaten_by(self) -> Shape {
+        Shape::ALL[(self as usize + 1) % 3]
+    }
+
+    pub fn score(self) -> u32 {
+        self as u32 + 1
+    }
+
+    // The shape to play against this one for the given outcome.
+    pub fn against(self, outcome: Outcome) -> Shape {
+        match outcome {
+            Outcome::Loss => self.beats(),
+            Outcome::Draw => self,
+            Outcome::Win => self.beaten_by(),
+        }
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(["rock", "paper", "scissors"][*self as usize])
+    }
+}
+
+// The outcome of a round for the player, in the order of their scores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+    // The outcome of playing `player` against `opponent`.
+    pub fn of(player: Shape, opponent: Shape) -> Outcome {
+        if player == opponent {
+            Outcome::Draw
+        } else if player.beats() == opponent {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    pub fn score(self) -> u32 {
+        self as u32 * 3
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(["lose", "draw", "win"][*self as usize])
+    }
+}
+
+// The letter in the second column of the strategy guide. What it means depends on the part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+impl Column {
+    pub const ALL: [Column; 3] = [Column::X, Column::Y, Column::Z];
+
+    // Part 1 reads the column as the shape to play.
+    pub fn shape(self) -> Shape {
+        Shape::ALL[self as usize]
+    }
+
+    // Part 2 reads the column as the outcome to aim for.
+    pub fn outcome(self) -> Outcome {
+        Outcome::ALL[self as usize]
+    }
+}
+
+// A round of the strategy guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub opponent: Shape,
+    pub column: Column,
+}
+
+// The score of playing `player` against `opponent`.
+pub fn score1(opponent: Shape, player: Shape) -> u32 {
+    player.score() + Outcome::of(player, opponent).score()
+}
+
+// The score of playing for `outcome` against `opponent`.
+pub fn score2(opponent: Shape, outcome: Outcome) -> u32 {
+    opponent.against(outcome).score() + outcome.score()
+}
+
+// Parse a round of the form "A Y".
+fn parse_round(line: &str) -> Result<Round, ParseError> {
+    let mut iter = line.split_whitespace();
+    let opponent = parse_column(
+        line,
+        next_field(line, &mut iter, "the opponent's play")?,
+        ['A', 'B', 'C'],
+        Shape::ALL,
+    )?;
+    let column = parse_column(
+        line,
+        next_field(line, &mut iter, "the player's column")?,
+        ['X', 'Y', 'Z'],
+        Column::ALL,
+    )?;
+    if let Some(word) = iter.next() {
+        return Err(ParseError::new(line, word, "expected end of line"));
+    }
+    Ok(Round { opponent, column })
+}
+
+// Parse a column of a round, which is one of the three given letters standing for the values in
+// the same order.
+fn parse_column<T: Copy>(
+    line: &str,
+    token: &str,
+    letters: [char; 3],
+    values: [T; 3],
+) -> Result<T, ParseError> {
+    let mut chars = token.chars();
+    let index = match (chars.next(), chars.next()) {
+        (Some(c), None) => letters.iter().position(|&letter| letter == c),
+        _ => None,
+    };
+    index.map(|i| values[i]).ok_or_else(|| {
+        ParseError::new(
+            line,
+            token,
+            format!("expected {}, {} or {}", letters[0], letters[1], letters[2]),
+        )
+    })
+}
+
+// Read the rounds of the strategy guide.
+pub fn parse_input(input: &str) -> Result<Vec<Round>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_round(line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+// Pass each of the rounds to the `score` function to accumulate the total score.
+fn total_score(rounds: &[Round], score: impl Fn(&Round) -> u32) -> u32 {
+    rounds.iter().map(score).sum::<u32>()
+}
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<Round>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    // Part 1: the second column is the shape to play.
+    fn part1(rounds: &Self::Input) -> u32 {
+        total_score(rounds, |round| score1(round.opponent, round.column.shape()))
+    }
+
+    // Part 2: the second column is the desired outcome.
+    fn part2(rounds: &Self::Input) -> u32 {
+        total_score(rounds, |round| {
+            score2(round.opponent, round.column.outcome())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use Column::*;
+    use Shape::*;
+
+    #[test]
+    fn test_score() {
+        let r1 = score1(Rock, Y.shape());
+        assert_eq!(r1, 8);
+        let r2 = score1(Paper, X.shape());
+        assert_eq!(r2, 1);
+        let r3 = score1(Scissors, Z.shape());
+        assert_eq!(r3, 6);
+        assert_eq!(r1 + r2 + r3, 15);
+    }
+
+    #[test]
+    fn test_score2() {
+        let r1 = score2(Rock, Y.outcome());
+        assert_eq!(r1, 4);
+        let r2 = score2(Paper, X.outcome());
+        assert_eq!(r2, 1);
+        let r3 = score2(Scissors, Z.outcome());
+        assert_eq!(r3, 7);
+        assert_eq!(r1 + r2 + r3, 12);
+    }
+
+    #[test]
+    fn test_rules() {
+        assert_eq!(Rock.beats(), Scissors);
+        assert_eq!(Paper.beats(), Rock);
+        assert_eq!(Scissors.beats(), Paper);
+        for shape in Shape::ALL {
+            assert_eq!(shape.beats().beaten_by(), shape);
+            assert_eq!(Outcome::of(shape, shape.beats()), Outcome::Win);
+            assert_eq!(Outcome::of(shape, shape.beaten_by()), Outcome::Loss);
+            for outcome in Outcome::ALL {
+                assert_eq!(Outcome::of(shape.against(outcome), shape), outcome);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_round() {
+        assert_eq!(
+            parse_round("B Z"),
+            Ok(Round {
+                opponent: Paper,
+                column: Z
+            })
+        );
+        let e = parse_round("D Z").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (1, "D"));
+        let e = parse_round("A XY").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (3, "XY"));
+        let e = parse_round("A X Y").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (5, "Y"));
+    }
+
+    fn rounds() -> impl Strategy<Value = Vec<Round>> {
+        prop::collection::vec(
+            (
+                prop::sample::select(Shape::ALL.to_vec()),
+                prop::sample::select(Column::ALL.to_vec()),
+            )
+                .prop_map(|(opponent, column)| Round { opponent, column }),
+            1..50,
+        )
+    }
+
+    fn render(rounds: &[Round]) -> String {
+        rounds
+            .iter()
+            .map(|round| {
+                format!(
+                    "{} {:?}\n",
+                    ['A', 'B', 'C'][round.opponent as usize],
+                    round.column
+                )
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(rounds in rounds()) {
+            prop_assert_eq!(parse_input(&render(&rounds)).unwrap(), rounds);
+        }
+
+        #[test]
+        fn test_invalid_play(
+            rounds in rounds(),
+            line in any::<prop::sample::Index>(),
+            column in 0..2usize,
+            junk in "[D-Wa-z0-9]",
+            append in any::<bool>(),
+        ) {
+            let i = line.index(rounds.len());
+            let mut lines: Vec<String> = render(&rounds).lines().map(String::from).collect();
+            let mut letters: Vec<String> = lines[i].split(' ').map(String::from).collect();
+            // Replace a column with a letter outside its range, or add a stray one to it.
+            if append {
+                letters[column].push_str(&junk);
+            } else {
+                letters[column] = junk;
+            }
+            lines[i] = letters.join(" ");
+            let e = parse_input(&lines.join("\n")).unwrap_err();
+            prop_assert_eq!((e.line, e.column), (i + 1, column * 2 + 1));
+        }
+
+        #[test]
+        fn test_no_panic(input in "[A-Z \n]{0,100}") {
+            let _ = parse_input(&input);
+        }
+    }
+}
@@ -1,76 +1,295 @@
-// Advent of Code 2022 - Day 2
-// Calculate the score of a Rock Paper Scissors game
+// Besides solving the puzzle, the strategy guide can be looked at in other ways with a subcommand:
+//
+//     day02 score [--test] [--rules RULES] [INPUT | -]
+//     day02 analyse [--test] [INPUT | -]
+//     day02 simulate [--test] [--player S] [--opponent S] [--rounds N] [--seed N] [INPUT | -]
+//     day02 optimise [--test] [--rules RULES] [INPUT | -]
+//
+// `score` scores the guide under the rules of another game, from a rules file such as
+// `data/rules/rpsls.txt` (the puzzle's own rules by default). `analyse` scores every reading of the
+// second column, to find the ones that score the most and the least. `simulate` plays a
+// tournament between two of the strategies in `day02::tournament`, some of which replay the guide.
+// `optimise` works out what to play against the opponent's shapes in the guide, and against an
+// opponent who plays as well as possible.
 
-#[allow(clippy::identity_op)]
-fn score1(opponent: char, player: char) -> u32 {
-    match (opponent, player) {
-        ('A', 'X') => 1 + 3,
-        ('A', 'Y') => 2 + 6,
-        ('A', 'Z') => 3 + 0,
-        ('B', 'X') => 1 + 0,
-        ('B', 'Y') => 2 + 3,
-        ('B', 'Z') => 3 + 6,
-        ('C', 'X') => 1 + 6,
-        ('C', 'Y') => 2 + 0,
-        ('C', 'Z') => 3 + 3,
-        _ => 0,
+use aoc_common::cli::{self, Source};
+use day02::analysis::Analysis;
+use day02::optimise::Optimisation;
+use day02::rules::{Reading, Rules};
+use day02::tournament::{self, STRATEGIES};
+use day02::{parse_input, Day02};
+use std::path::{Path, PathBuf};
+use std::process;
+
+const USAGE: &str = "Usage: day02 score [--test] [--rules RULES] [INPUT | -]\n       \
+    day02 analyse [--test] [INPUT | -]\n       \
+    day02 simulate [--test] [--player S] [--opponent S] [--rounds N] [--seed N] [INPUT | -]\n       \
+    day02 optimise [--test] [--rules RULES] [INPUT | -]\n\n\
+    score scores the strategy guide under the rules of the game in RULES (see data/rules), like\n\
+    the two parts of the puzzle do. analyse scores every mapping of X, Y and Z to the shapes or\n\
+    to the outcomes, and points out the best and the worst. simulate plays N rounds (as many as\n\
+    the guide has by default) between the strategies S below (part1 against guide by default).\n\
+    optimise finds the best responses to the shapes the opponent plays in the guide, and the\n\
+    Nash equilibrium of the game in RULES.\n\n\
+    Strategies:";
+
+// The usage, with the strategies that `simulate` knows.
+fn usage() -> String {
+    let width = STRATEGIES
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let strategies: Vec<String> = STRATEGIES
+        .iter()
+        .map(|(name, description)| format!("    {:<w$}  {}", name, description, w = width))
+        .collect();
+    format!("{}\n{}", USAGE, strategies.join("\n"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    Score {
+        rules: Option<PathBuf>,
+    },
+    Analyse,
+    Simulate {
+        player: String,
+        opponent: String,
+        rounds: Option<usize>,
+        seed: u64,
+    },
+    Optimise {
+        rules: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    command: Command,
+    source: Source,
+}
+
+// Parse the arguments following the name of the subcommand `command`.
+fn parse_args<I: Iterator<Item = String>>(
+    command: &str,
+    args: I,
+    crate_dir: impl AsRef<Path>,
+) -> Result<Options, String> {
+    let mut command = match command {
+        "score" => Command::Score { rules: None },
+        "analyse" => Command::Analyse,
+        "simulate" => Command::Simulate {
+            player: String::from("part1"),
+            opponent: String::from("guide"),
+            rounds: None,
+            seed: 1,
+        },
+        "optimise" => Command::Optimise { rules: None },
+        _ => return Err(format!("Unknown command: {}", command)),
+    };
+    let source = cli::parse_command(args, crate_dir, |arg, args| {
+        match (arg, &mut command) {
+            ("-r" | "--rules", Command::Score { rules } | Command::Optimise { rules }) => {
+                *rules = Some(PathBuf::from(args.value(arg)?));
+            }
+            ("-p" | "--player", Command::Simulate { player: name, .. })
+            | ("-o" | "--opponent", Command::Simulate { opponent: name, .. }) => {
+                let v = args.value(arg)?;
+                if !STRATEGIES.iter().any(|&(strategy, _)| strategy == v) {
+                    return Err(format!("Unknown strategy: {}", v));
+                }
+                *name = v;
+            }
+            ("-n" | "--rounds", Command::Simulate { rounds, .. }) => {
+                let v = args.value(arg)?;
+                *rounds = Some(
+                    v.parse()
+                        .map_err(|_| format!("Invalid number of rounds: {}", v))?,
+                );
+            }
+            ("--seed", Command::Simulate { seed, .. }) => {
+                let v = args.value(arg)?;
+                *seed = v.parse().map_err(|_| format!("Invalid seed: {}", v))?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    Ok(Options { command, source })
+}
+
+// Read the rules from `path`, or use the puzzle's own.
+fn load_rules(path: Option<&PathBuf>) -> Result<Rules, String> {
+    match path {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)
+                .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+            Rules::parse(&text).map_err(|e| e.in_file(path).to_string())
+        }
+        None => Ok(Rules::standard()),
     }
 }
 
-fn score2(opponent: char, player: char) -> u32 {
-    match (opponent, player) {
-        ('A', 'X') => 0 + 3,
-        ('A', 'Y') => 3 + 1,
-        ('A', 'Z') => 6 + 2,
-        ('B', 'X') => 0 + 1,
-        ('B', 'Y') => 3 + 2,
-        ('B', 'Z') => 6 + 3,
-        ('C', 'X') => 0 + 2,
-        ('C', 'Y') => 3 + 3,
-        ('C', 'Z') => 6 + 1,
-        _ => 0,
+fn run(options: &Options) -> Result<String, String> {
+    let name = options.source.name();
+    let input = options
+        .source
+        .read()
+        .map_err(|e| format!("could not read {}: {}", name.display(), e))?;
+    match &options.command {
+        Command::Score { rules } => {
+            let rules = load_rules(rules.as_ref())?;
+            let mut report = String::new();
+            for (part, reading) in [(1, Reading::Shape), (2, Reading::Outcome)] {
+                let score = rules
+                    .score_guide(&input, reading)
+                    .map_err(|e| e.in_file(&name).to_string())?;
+                report += &format!("Part {}: {}\n", part, score);
+            }
+            Ok(report)
+        }
+        Command::Analyse => {
+            let rounds = parse_input(&input).map_err(|e| e.in_file(&name).to_string())?;
+            Ok(Analysis::new(&rounds).to_string())
+        }
+        Command::Simulate {
+            player,
+            opponent,
+            rounds,
+            seed,
+        } => {
+            let guide = parse_input(&input).map_err(|e| e.in_file(&name).to_string())?;
+            // Two random players shouldn't play the same shapes.
+            let mut a = tournament::strategy(player, &guide, *seed)?;
+            let mut b = tournament::strategy(opponent, &guide, seed.wrapping_add(1))?;
+            let rounds = rounds.unwrap_or(guide.len());
+            let result = tournament::simulate(a.as_mut(), b.as_mut(), rounds);
+            Ok(format!(
+                "{} rounds\nplayer ({}): {}\nopponent ({}): {}\n",
+                rounds, player, result.player, opponent, result.opponent
+            ))
+        }
+        Command::Optimise { rules } => {
+            let rules = load_rules(rules.as_ref())?;
+            let counts = rules
+                .count_opponents(&input)
+                .map_err(|e| e.in_file(&name).to_string())?;
+            if counts.iter().all(|&count| count == 0) {
+                return Err(format!("{}: the guide has no rounds", name.display()));
+            }
+            Ok(Optimisation::new(&rules, &counts).to_string())
+        }
     }
 }
 
 fn main() {
-    // Read the input data from `data/input.txt`, and pass the plays to the `score` function
-    // to accumulate the total score.
-    let total_score = std::fs::read_to_string("data/input.txt")
-        .unwrap()
-        .lines()
-        .map(|line| {
-            let mut iter = line.split_whitespace();
-            let opponent = iter.next().unwrap().chars().next().unwrap();
-            let player = iter.next().unwrap().chars().next().unwrap();
-            score2(opponent, player)
-        })
-        .sum::<u32>();
-    println!("{}", total_score);
+    let crate_dir = env!("CARGO_MANIFEST_DIR");
+    let mut args = std::env::args().skip(1).peekable();
+    if let Some(command) =
+        args.next_if(|arg| ["score", "analyse", "simulate", "optimise"].contains(&arg.as_str()))
+    {
+        let options = parse_args(&command, args, crate_dir).unwrap_or_else(|e| {
+            eprintln!("error: {}\n{}", e, usage());
+            process::exit(2);
+        });
+        match run(&options) {
+            Ok(report) => print!("{}", report),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    aoc_common::cli::run::<Day02>(crate_dir, |part, answer| {
+        format!("Part {}: {}", part, answer)
+    });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn options(command: &str, s: &str) -> Result<Options, String> {
+        parse_args(
+            command,
+            s.split_whitespace().map(String::from),
+            env!("CARGO_MANIFEST_DIR"),
+        )
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            options("score", "--rules rules.txt -").unwrap(),
+            Options {
+                command: Command::Score {
+                    rules: Some(PathBuf::from("rules.txt"))
+                },
+                source: Source::Stdin,
+            }
+        );
+        assert!(options("score", "--rules").is_err());
+        assert!(options("score", "--test input.txt").is_err());
+        assert_eq!(options("analyse", "").unwrap().command, Command::Analyse);
+        assert!(options("analyse", "--rules rules.txt").is_err());
+        assert_eq!(
+            options(
+                "simulate",
+                "-p markov --opponent random --rounds 10 --seed 7"
+            )
+            .unwrap()
+            .command,
+            Command::Simulate {
+                player: String::from("markov"),
+                opponent: String::from("random"),
+                rounds: Some(10),
+                seed: 7,
+            }
+        );
+        assert!(options("simulate", "--player psychic").is_err());
+        assert!(options("simulate", "--rounds -1").is_err());
+        assert_eq!(
+            options("optimise", "--rules rules.txt").unwrap().command,
+            Command::Optimise {
+                rules: Some(PathBuf::from("rules.txt"))
+            }
+        );
+    }
+
     #[test]
-    fn test_score() {
-        let r1 = score1('A', 'Y');
-        assert_eq!(r1, 8);
-        let r2 = score1('B', 'X');
-        assert_eq!(r2, 1);
-        let r3 = score1('C', 'Z');
-        assert_eq!(r3, 6);
-        assert_eq!(r1 + r2 + r3, 15);
+    fn test_usage() {
+        // Each strategy has a line of its own.
+        let usage = usage();
+        for (name, description) in STRATEGIES {
+            assert!(usage.lines().any(|line| {
+                line.split_whitespace().next() == Some(name) && line.ends_with(description)
+            }));
+        }
     }
 
     #[test]
-    fn test_score2() {
-        let r1 = score2('A', 'Y');
-        assert_eq!(r1, 4);
-        let r2 = score2('B', 'X');
-        assert_eq!(r2, 1);
-        let r3 = score2('C', 'Z');
-        assert_eq!(r3, 7);
-        assert_eq!(r1 + r2 + r3, 12);
+    fn test_run() {
+        let report = run(&options("score", "--test").unwrap()).unwrap();
+        assert_eq!(report, "Part 1: 15\nPart 2: 12\n");
+        let rules = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/rules/rpsls.txt");
+        let args = format!("--test --rules {}", rules.display());
+        let report = run(&options("score", &args).unwrap()).unwrap();
+        assert_eq!(report, "Part 1: 15\nPart 2: 20\n");
+        let report = run(&options("analyse", "--test").unwrap()).unwrap();
+        assert_eq!(report.lines().count(), 12);
+        let report = run(&options("simulate", "--test").unwrap()).unwrap();
+        assert_eq!(
+            report,
+            "3 rounds\n\
+             player (part1): wins 33.3%, draws 33.3%, losses 33.3%, score 15\n\
+             opponent (guide): wins 33.3%, draws 33.3%, losses 33.3%, score 15\n"
+        );
+        let report = run(&options("optimise", "--test").unwrap()).unwrap();
+        assert!(report.contains("best pure response: scissors 100.0%, 6.000 per round\n"));
+        let report = run(&options("optimise", &args).unwrap()).unwrap();
+        assert!(report.contains("    spock     "));
     }
 }
@@ -0,0 +1,359 @@
+// Choosing what to play, for `day02 optimise`, with the scores of part 1 under any rules.
+//
+// Against an opponent who plays each shape with a known probability (the proportions of the
+// guide's opponent column), the expected score of a mix of shapes is the same mix of the expected
+// scores of the shapes, so no mix does better than the best shape on its own: the best mixed
+// responses are the mixes of the shapes that tie for the best.
+//
+// Against an opponent who also plays as well as they can, the game is a symmetric two-player game
+// with the payoffs of `Rules::score1`. It isn't zero-sum, since each side scores its own shape,
+// so the equilibrium isn't simply uniform. It's found by support enumeration: for each set of
+// shapes, the mix over them that leaves the other side indifferent between them is solved for,
+// and kept if no shape outside the set would do better against it. There are 2^N sets, so only
+// games with up to `MAX_SHAPES` shapes are searched.
+
+use crate::rules::Rules;
+use std::fmt;
+
+// Probabilities within this of each other are taken to be equal.
+const EPSILON: f64 = 1e-9;
+
+// The most shapes a game can have for its equilibrium to be searched for, which takes a moment
+// at this size.
+pub const MAX_SHAPES: usize = 15;
+
+// The expected score per round of each shape against an opponent playing `distribution`.
+pub fn expected_scores(rules: &Rules, distribution: &[f64]) -> Vec<f64> {
+    (0..rules.shapes().len())
+        .map(|player| {
+            distribution
+                .iter()
+                .enumerate()
+                .map(|(opponent, p)| p * rules.score1(opponent, player) as f64)
+                .sum()
+        })
+        .collect()
+}
+
+// A mix of shapes, and the score per round it expects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Policy {
+    // The probability of playing each shape.
+    pub mix: Vec<f64>,
+    pub expected: f64,
+}
+
+// The best responses to an opponent playing `distribution`: the best shape, and the uniform mix
+// of all of the shapes that tie with it.
+pub fn best_responses(rules: &Rules, distribution: &[f64]) -> (Policy, Policy) {
+    let scores = expected_scores(rules, distribution);
+    let best = scores.iter().copied().fold(f64::MIN, f64::max);
+    let ties: Vec<bool> = scores.iter().map(|&s| best - s < EPSILON).collect();
+    let pure = Policy {
+        mix: ties
+            .iter()
+            .scan(false, |found, &tie| {
+                let first = tie && !*found;
+                *found |= tie;
+                Some(if first { 1.0 } else { 0.0 })
+            })
+            .collect(),
+        expected: best,
+    };
+    let count = ties.iter().filter(|&&tie| tie).count() as f64;
+    let mixed = Policy {
+        mix: ties
+            .iter()
+            .map(|&tie| if tie { 1.0 / count } else { 0.0 })
+            .collect(),
+        expected: best,
+    };
+    (pure, mixed)
+}
+
+// A symmetric Nash equilibrium of the game: a mix that, played by both sides, leaves neither with
+// a better response. Its `expected` is the score per round of each side. This is `None` only if
+// the game is degenerate in a way that support enumeration misses, and an error if the game has
+// more than `MAX_SHAPES` shapes.
+pub fn equilibrium(rules: &Rules) -> Result<Option<Policy>, String> {
+    let n = rules.shapes().len();
+    if n > MAX_SHAPES {
+        return Err(format!(
+            "the game has {} shapes, more than the {} that can be searched",
+            n, MAX_SHAPES
+        ));
+    }
+    let mut supports: Vec<u32> = (1..1u32 << n).collect();
+    supports.sort_by_key(|support| support.count_ones());
+    Ok(supports.into_iter().find_map(|support| {
+        let shapes: Vec<usize> = (0..n).filter(|i| support & (1 << i) != 0).collect();
+        let k = shapes.len();
+        // The unknowns are the probabilities of the shapes, and the score v of each of them:
+        // every shape in the support scores v against the mix, and the probabilities add up to 1.
+        let mut a = vec![vec![0.0; k + 1]; k + 1];
+        let mut b = vec![0.0; k + 1];
+        for (row, &player) in shapes.iter().enumerate() {
+            for (column, &opponent) in shapes.iter().enumerate() {
+                a[row][column] = rules.score1(opponent, player) as f64;
+            }
+            a[row][k] = -1.0;
+        }
+        a[k][..k].fill(1.0);
+        b[k] = 1.0;
+        let solution = solve(a, b)?;
+        if solution[..k].iter().any(|&p| p < -EPSILON) {
+            return None;
+        }
+        let mut mix = vec![0.0; n];
+        for (&shape, &p) in shapes.iter().zip(&solution) {
+            mix[shape] = p.max(0.0);
+        }
+        let expected = solution[k];
+        let scores = expected_scores(rules, &mix);
+        if scores.iter().any(|&score| score > expected + EPSILON) {
+            return None;
+        }
+        Some(Policy { mix, expected })
+    }))
+}
+
+// Solve `a x = b` by Gaussian elimination with partial pivoting, or `None` if `a` is singular.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for column in 0..n {
+        let pivot =
+            (column..n).max_by(|&i, &j| a[i][column].abs().total_cmp(&a[j][column].abs()))?;
+        if a[pivot][column].abs() < EPSILON {
+            return None;
+        }
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+        for row in column + 1..n {
+            let (above, below) = a.split_at_mut(row);
+            let factor = below[0][column] / above[column][column];
+            for (x, &y) in below[0][column..].iter_mut().zip(&above[column][column..]) {
+                *x -= factor * y;
+            }
+            b[row] -= factor * b[column];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|i| a[row][i] * x[i]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
+
+// The policies for a strategy guide.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Optimisation {
+    pub names: Vec<String>,
+    // The proportion of the rounds in which the opponent plays each shape.
+    pub distribution: Vec<f64>,
+    pub expected: Vec<f64>,
+    pub pure: Policy,
+    pub mixed: Policy,
+    // Why the equilibrium wasn't searched for, if the game is too large.
+    pub equilibrium: Result<Option<Policy>, String>,
+}
+
+impl Optimisation {
+    // Optimise against the opponent's shapes counted in `counts`, of which there must be some.
+    pub fn new(rules: &Rules, counts: &[u64]) -> Optimisation {
+        let total: u64 = counts.iter().sum();
+        let distribution: Vec<f64> = counts.iter().map(|&c| c as f64 / total as f64).collect();
+        let (pure, mixed) = best_responses(rules, &distribution);
+        Optimisation {
+            names: rules
+                .shapes()
+                .iter()
+                .map(|shape| shape.name.clone())
+                .collect(),
+            expected: expected_scores(rules, &distribution),
+            distribution,
+            pure,
+            mixed,
+            equilibrium: equilibrium(rules),
+        }
+    }
+
+    // The shapes in `mix` that are played, with their probabilities.
+    fn describe(&self, mix: &[f64]) -> String {
+        let shapes: Vec<String> = mix
+            .iter()
+            .zip(&self.names)
+            .filter(|(&p, _)| p > EPSILON)
+            .map(|(p, name)| format!("{} {:.1}%", name, p * 100.0))
+            .collect();
+        shapes.join(", ")
+    }
+}
+
+impl fmt::Display for Optimisation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "opponent: {}", self.describe(&self.distribution))?;
+        let width = self.names.iter().map(String::len).max().unwrap_or(0);
+        writeln!(f, "expected score per round:")?;
+        for (name, expected) in self.names.iter().zip(&self.expected) {
+            write!(f, "    {:<w$}  {:.3}", name, expected, w = width)?;
+            if self.pure.expected - expected < EPSILON {
+                write!(f, "  <- best")?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(
+            f,
+            "best pure response: {}, {:.3} per round",
+            self.describe(&self.pure.mix),
+            self.pure.expected
+        )?;
+        writeln!(
+            f,
+            "best mixed response: {}, {:.3} per round",
+            self.describe(&self.mixed.mix),
+            self.mixed.expected
+        )?;
+        match &self.equilibrium {
+            Ok(Some(equilibrium)) => writeln!(
+                f,
+                "nash equilibrium: {}, {:.3} per round",
+                self.describe(&equilibrium.mix),
+                equilibrium.expected
+            ),
+            Ok(None) => writeln!(f, "nash equilibrium: not found"),
+            Err(e) => writeln!(f, "nash equilibrium: skipped, {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const RPSLS: &str = include_str!("../data/rules/rpsls.txt");
+    const RPS7: &str = include_str!("../data/rules/rps7.txt");
+
+    // The rules of a balanced game with `n` shapes, each of which beats the next half of them.
+    fn cyclic(n: usize) -> String {
+        let symbol = |base: u32, i: usize| char::from_u32(base + i as u32).unwrap();
+        let mut text = String::new();
+        for i in 0..n {
+            let (opponent, player) = (symbol(0x100, i), symbol(0x200, i));
+            text += &format!("shape s{} {} {} {}\n", i, opponent, player, i % 3 + 1);
+        }
+        for i in 0..n {
+            let beaten: Vec<String> = (1..=n / 2).map(|j| format!("s{}", (i + j) % n)).collect();
+            text += &format!("s{} beats {}\n", i, beaten.join(" "));
+        }
+        text + "lose X 0\ndraw Y 3\nwin Z 6\n"
+    }
+
+    // Check that `policy` is a symmetric equilibrium of the game.
+    fn assert_equilibrium(rules: &Rules, policy: &Policy) {
+        assert!((policy.mix.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        assert!(policy.mix.iter().all(|&p| p >= 0.0));
+        let scores = expected_scores(rules, &policy.mix);
+        let best = scores.iter().copied().fold(f64::MIN, f64::max);
+        assert!((best - policy.expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_best_responses() {
+        let rules = Rules::standard();
+        // Against rock every time, paper wins 8 points a round.
+        let (pure, mixed) = best_responses(&rules, &[1.0, 0.0, 0.0]);
+        assert_eq!(pure.mix, vec![0.0, 1.0, 0.0]);
+        assert_eq!(pure, mixed);
+        assert_eq!(pure.expected, 8.0);
+        // Rock and paper in equal measure: paper scores (8 + 5) / 2, and scissors (3 + 9) / 2.
+        let (pure, mixed) = best_responses(&rules, &[0.5, 0.5, 0.0]);
+        assert_eq!(pure.mix, vec![0.0, 1.0, 0.0]);
+        assert_eq!(pure.expected, 6.5);
+        assert_eq!(mixed.expected, 6.5);
+        // Paper and scissors tie when rock is a little rarer.
+        let (pure, mixed) = best_responses(&rules, &[4.0 / 9.0, 5.0 / 9.0, 0.0]);
+        assert_eq!(pure.mix, vec![0.0, 1.0, 0.0]);
+        assert_eq!(mixed.mix, vec![0.0, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_equilibrium() {
+        let rules = Rules::standard();
+        let policy = equilibrium(&rules).unwrap().unwrap();
+        assert_equilibrium(&rules, &policy);
+        // Every shape has to be played some of the time, or another would beat the mix.
+        assert!(policy.mix.iter().all(|&p| p > 0.0));
+
+        for text in [RPSLS, RPS7] {
+            let rules = Rules::parse(text).unwrap();
+            assert_equilibrium(&rules, &equilibrium(&rules).unwrap().unwrap());
+        }
+
+        // When the shapes all score the same, the balanced game is fair, so the equilibrium is
+        // uniform, with half of the outcome points to each side.
+        let text = RPSLS.replace(" 2\n", " 1\n").replace(" 3\n", " 1\n");
+        let text = text.replace(" 4\n", " 1\n").replace(" 5\n", " 1\n");
+        let rules = Rules::parse(&text.replace("draw Y 1", "draw Y 3")).unwrap();
+        let policy = equilibrium(&rules).unwrap().unwrap();
+        assert!(policy.mix.iter().all(|&p| (p - 0.2).abs() < 1e-9));
+        assert!((policy.expected - 4.0).abs() < 1e-9);
+
+        // The largest games that are searched, and ones too large to be.
+        let rules = Rules::parse(&cyclic(MAX_SHAPES)).unwrap();
+        assert_equilibrium(&rules, &equilibrium(&rules).unwrap().unwrap());
+        for n in [MAX_SHAPES + 2, 33, 101] {
+            let rules = Rules::parse(&cyclic(n)).unwrap();
+            assert!(equilibrium(&rules).is_err());
+            // The responses to the guide are still worked out. Against every shape in turn, each
+            // wins and loses as often, so the shapes that score 3 are the best.
+            let optimisation = Optimisation::new(&rules, &vec![1; n]);
+            assert!(optimisation.equilibrium.is_err());
+            assert!((optimisation.pure.expected - 6.0).abs() < 1e-9);
+            assert_eq!(optimisation.pure.mix[2], 1.0);
+            let report = optimisation.to_string();
+            assert!(report.contains("best pure response: s2 100.0%, 6.000 per round\n"));
+            assert!(report.ends_with(&format!(
+                "nash equilibrium: skipped, the game has {} shapes, more than the 15 that can be \
+                 searched\n",
+                n
+            )));
+        }
+    }
+
+    #[test]
+    fn test_optimisation() {
+        let rules = Rules::standard();
+        let optimisation = Optimisation::new(&rules, &[1, 1, 1]);
+        assert_eq!(
+            optimisation
+                .expected
+                .iter()
+                .map(|e| (e * 3.0).round())
+                .collect::<Vec<_>>(),
+            vec![12.0, 15.0, 18.0]
+        );
+        let report = optimisation.to_string();
+        assert!(report.starts_with("opponent: rock 33.3%, paper 33.3%, scissors 33.3%\n"));
+        assert!(report.contains("    scissors  6.000  <- best\n"));
+        assert!(report.contains("best pure response: scissors 100.0%, 6.000 per round\n"));
+        assert!(report.contains("nash equilibrium: rock "));
+    }
+
+    proptest! {
+        #[test]
+        fn test_no_better_mix(
+            counts in prop::collection::vec(0..100u64, 3),
+            mix in prop::collection::vec(0..100u64, 3),
+        ) {
+            prop_assume!(counts.iter().sum::<u64>() > 0 && mix.iter().sum::<u64>() > 0);
+            let rules = Rules::standard();
+            let optimisation = Optimisation::new(&rules, &counts);
+            let total = mix.iter().sum::<u64>() as f64;
+            let mix: Vec<f64> = mix.iter().map(|&m| m as f64 / total).collect();
+            let score: f64 = mix.iter().zip(&optimisation.expected).map(|(m, e)| m * e).sum();
+            prop_assert!(score <= optimisation.pure.expected + 1e-9);
+        }
+    }
+}
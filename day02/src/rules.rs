@@ -0,0 +1,454 @@
+// Generalised cyclic games, such as Rock Paper Scissors Lizard Spock, read from a rules file (see
+// `data/rules/rps.txt` for the format). Any odd number of shapes can be played, as long as the
+// tournament is balanced: every pair of shapes has a winner, and each shape beats exactly half of
+// the others, so that no shape is better than another.
+
+use crate::Outcome;
+use aoc_common::error::{next_field, parse_field};
+use aoc_common::ParseError;
+
+const STANDARD: &str = include_str!("../data/rules/rps.txt");
+
+// The largest score of a shape or an outcome, so that a round's score can't overflow, and a
+// guide's total can't either until it has trillions of rounds.
+pub const MAX_SCORE: u32 = 1_000_000;
+
+// A shape, with the symbols standing for it in the opponent's and the player's columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeRule {
+    pub name: String,
+    pub opponent: char,
+    pub player: char,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutcomeRule {
+    pub symbol: char,
+    pub score: u32,
+}
+
+// What the second column of the strategy guide stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reading {
+    // The shape to play, as in part 1.
+    Shape,
+    // The outcome to aim for, as in part 2.
+    Outcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    shapes: Vec<ShapeRule>,
+    // `beats[i][j]` is whether shape `i` beats shape `j`.
+    beats: Vec<Vec<bool>>,
+    // Indexed by `Outcome`.
+    outcomes: [OutcomeRule; 3],
+}
+
+impl Rules {
+    // The rules of the puzzle, which score the same as `score1` and `score2`.
+    pub fn standard() -> Rules {
+        Rules::parse(STANDARD).unwrap()
+    }
+
+    pub fn parse(input: &str) -> Result<Rules, ParseError> {
+        let mut shapes: Vec<ShapeRule> = Vec::new();
+        // The line each shape was defined on, to report the shapes that are unbalanced.
+        let mut defined = Vec::new();
+        let mut wins: Vec<(usize, &str, &str, &str)> = Vec::new();
+        let mut outcomes = [None; 3];
+        for (i, line) in input.lines().enumerate() {
+            let at = |e: ParseError| e.at_line(i + 1);
+            let mut iter = line.split_whitespace();
+            let Some(keyword) = iter.next() else {
+                continue;
+            };
+            match keyword {
+                _ if keyword.starts_with('#') => continue,
+                "shape" => {
+                    let name = next_field(line, &mut iter, "the name of the shape").map_err(at)?;
+                    if shapes.iter().any(|shape| shape.name == name) {
+                        return Err(at(ParseError::new(line, name, "shape defined twice")));
+                    }
+                    let opponent =
+                        parse_symbol(line, &mut iter, "the opponent's symbol").map_err(at)?;
+                    let player =
+                        parse_symbol(line, &mut iter, "the player's symbol").map_err(at)?;
+                    let used = |symbol: (&str, char), column: fn(&ShapeRule) -> char| {
+                        if shapes.iter().map(column).any(|c| c == symbol.1) {
+                            Err(at(ParseError::new(line, symbol.0, "symbol used twice")))
+                        } else {
+                            Ok(())
+                        }
+                    };
+                    used(opponent, |shape| shape.opponent)?;
+                    used(player, |shape| shape.player)?;
+                    let score = next_field(line, &mut iter, "a score")
+                        .and_then(|token| parse_score(line, token))
+                        .map_err(at)?;
+                    shapes.push(ShapeRule {
+                        name: name.to_string(),
+                        opponent: opponent.1,
+                        player: player.1,
+                        score,
+                    });
+                    defined.push((i + 1, line, name));
+                }
+                "lose" | "draw" | "win" => {
+                    let outcome = match keyword {
+                        "lose" => Outcome::Loss,
+                        "draw" => Outcome::Draw,
+                        _ => Outcome::Win,
+                    };
+                    let symbol =
+                        parse_symbol(line, &mut iter, "the outcome's symbol").map_err(at)?;
+                    if outcomes
+                        .iter()
+                        .flatten()
+                        .any(|o: &OutcomeRule| o.symbol == symbol.1)
+                    {
+                        return Err(at(ParseError::new(line, symbol.0, "symbol used twice")));
+                    }
+                    let score = next_field(line, &mut iter, "a score")
+                        .and_then(|token| parse_score(line, token))
+                        .map_err(at)?;
+                    if outcomes[outcome as usize].is_some() {
+                        return Err(at(ParseError::new(line, keyword, "outcome defined twice")));
+                    }
+                    outcomes[outcome as usize] = Some(OutcomeRule {
+                        symbol: symbol.1,
+                        score,
+                    });
+                }
+                _ => {
+                    let verb = next_field(line, &mut iter, "\"beats\"").map_err(at)?;
+                    if verb != "beats" {
+                        return Err(at(ParseError::new(line, verb, "expected \"beats\"")));
+                    }
+                    let count = wins.len();
+                    wins.extend(iter.by_ref().map(|loser| (i + 1, line, keyword, loser)));
+                    if wins.len() == count {
+                        return Err(at(ParseError::missing(line, "expected a shape")));
+                    }
+                }
+            }
+            if let Some(word) = iter.next() {
+                return Err(at(ParseError::new(line, word, "expected end of line")));
+            }
+        }
+
+        // The end of the file is where anything missing is reported.
+        let end = input.lines().count() + 1;
+        let missing = |message: String| ParseError::missing("", message).at_line(end);
+        let n = shapes.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(missing(format!(
+                "expected an odd number of shapes, at least 3, not {}",
+                n
+            )));
+        }
+        let [Some(loss), Some(draw), Some(win)] = outcomes else {
+            let keyword =
+                ["lose", "draw", "win"][outcomes.iter().position(Option::is_none).unwrap()];
+            return Err(missing(format!("expected a \"{}\" rule", keyword)));
+        };
+
+        let index = |line: &str, name: &str| {
+            shapes
+                .iter()
+                .position(|shape| shape.name == name)
+                .ok_or_else(|| ParseError::new(line, name, "unknown shape"))
+        };
+        let mut beats = vec![vec![false; n]; n];
+        for (number, line, winner, loser) in wins {
+            let at = |e: ParseError| e.at_line(number);
+            let (i, j) = (
+                index(line, winner).map_err(at)?,
+                index(line, loser).map_err(at)?,
+            );
+            if i == j {
+                return Err(at(ParseError::new(
+                    line,
+                    loser,
+                    "a shape can't beat itself",
+                )));
+            } else if beats[j][i] {
+                return Err(at(ParseError::new(
+                    line,
+                    loser,
+                    format!("{} already beats {}", loser, winner),
+                )));
+            }
+            beats[i][j] = true;
+        }
+        for (i, (number, line, name)) in defined.into_iter().enumerate() {
+            let count = beats[i].iter().filter(|&&b| b).count();
+            if count != n / 2 {
+                return Err(ParseError::new(
+                    line,
+                    name,
+                    format!(
+                        "each shape must beat {} of the others, but this one beats {}",
+                        n / 2,
+                        count
+                    ),
+                )
+                .at_line(number));
+            }
+        }
+        Ok(Rules {
+            shapes,
+            beats,
+            outcomes: [loss, draw, win],
+        })
+    }
+
+    pub fn shapes(&self) -> &[ShapeRule] {
+        &self.shapes
+    }
+
+    pub fn outcome_rule(&self, outcome: Outcome) -> OutcomeRule {
+        self.outcomes[outcome as usize]
+    }
+
+    // Whether shape `i` beats shape `j`.
+    pub fn beats(&self, i: usize, j: usize) -> bool {
+        self.beats[i][j]
+    }
+
+    // The outcome of playing shape `player` against shape `opponent`.
+    pub fn outcome(&self, player: usize, opponent: usize) -> Outcome {
+        if player == opponent {
+            Outcome::Draw
+        } else if self.beats[player][opponent] {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    // The score of playing shape `player` against shape `opponent`.
+    pub fn score1(&self, opponent: usize, player: usize) -> u32 {
+        self.shapes[player].score + self.outcome_rule(self.outcome(player, opponent)).score
+    }
+
+    // The score of playing for `outcome` against shape `opponent`. With more than three shapes
+    // there's more than one shape that wins or loses, and the one scoring the most is played.
+    pub fn score2(&self, opponent: usize, outcome: Outcome) -> u32 {
+        (0..self.shapes.len())
+            .filter(|&player| self.outcome(player, opponent) == outcome)
+            .map(|player| self.score1(opponent, player))
+            .max()
+            .unwrap()
+    }
+
+    // The total score of the strategy guide in `input`, reading its second column as `reading`.
+    pub fn score_guide(&self, input: &str, reading: Reading) -> Result<u64, ParseError> {
+        let opponents: Vec<char> = self.shapes.iter().map(|shape| shape.opponent).collect();
+        let players: Vec<char> = match reading {
+            Reading::Shape => self.shapes.iter().map(|shape| shape.player).collect(),
+            Reading::Outcome => self.outcomes.iter().map(|outcome| outcome.symbol).collect(),
+        };
+        let mut total = 0;
+        for (i, line) in input.lines().enumerate() {
+            let at = |e: ParseError| e.at_line(i + 1);
+            let mut iter = line.split_whitespace();
+            let opponent = next_field(line, &mut iter, "the opponent's play")
+                .and_then(|token| find_symbol(line, token, &opponents))
+                .map_err(at)?;
+            let player = next_field(line, &mut iter, "the player's column")
+                .and_then(|token| find_symbol(line, token, &players))
+                .map_err(at)?;
+            if let Some(word) = iter.next() {
+                return Err(at(ParseError::new(line, word, "expected end of line")));
+            }
+            total += match reading {
+                Reading::Shape => self.score1(opponent, player),
+                Reading::Outcome => self.score2(opponent, Outcome::ALL[player]),
+            } as u64;
+        }
+        Ok(total)
+    }
+
+    // How many times the opponent plays each shape in the strategy guide in `input`. Only the
+    // opponent's column is read.
+    pub fn count_opponents(&self, input: &str) -> Result<Vec<u64>, ParseError> {
+        let opponents: Vec<char> = self.shapes.iter().map(|shape| shape.opponent).collect();
+        let mut counts = vec![0; opponents.len()];
+        for (i, line) in input.lines().enumerate() {
+            let opponent = next_field(line, &mut line.split_whitespace(), "the opponent's play")
+                .and_then(|token| find_symbol(line, token, &opponents))
+                .map_err(|e| e.at_line(i + 1))?;
+            counts[opponent] += 1;
+        }
+        Ok(counts)
+    }
+}
+
+fn parse_score(line: &str, token: &str) -> Result<u32, ParseError> {
+    let score = parse_field(line, token, "a score")?;
+    if score > MAX_SCORE {
+        return Err(ParseError::new(
+            line,
+            token,
+            format!("expected a score of at most {}", MAX_SCORE),
+        ));
+    }
+    Ok(score)
+}
+
+// Parse a symbol, which is a single character, returning the token too for reporting errors.
+fn parse_symbol<'a, I: Iterator<Item = &'a str>>(
+    line: &str,
+    tokens: &mut I,
+    what: &str,
+) -> Result<(&'a str, char), ParseError> {
+    let token = next_field(line, tokens, what)?;
+    let mut chars = token.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok((token, c)),
+        _ => Err(ParseError::new(line, token, "expected a single character")),
+    }
+}
+
+// Find which of `symbols` the token is.
+fn find_symbol(line: &str, token: &str, symbols: &[char]) -> Result<usize, ParseError> {
+    let mut chars = token.chars();
+    let index = match (chars.next(), chars.next()) {
+        (Some(c), None) => symbols.iter().position(|&symbol| symbol == c),
+        _ => None,
+    };
+    index.ok_or_else(|| {
+        let symbols: Vec<String> = symbols.iter().map(char::to_string).collect();
+        ParseError::new(
+            line,
+            token,
+            format!("expected one of {}", symbols.join(", ")),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{score1, score2, Shape};
+
+    const TEST_INPUT: &str = include_str!("../data/test.txt");
+    const RPSLS: &str = include_str!("../data/rules/rpsls.txt");
+    const RPS7: &str = include_str!("../data/rules/rps7.txt");
+
+    #[test]
+    fn test_standard() {
+        let rules = Rules::standard();
+        for opponent in Shape::ALL {
+            for player in Shape::ALL {
+                assert_eq!(
+                    rules.score1(opponent as usize, player as usize),
+                    score1(opponent, player)
+                );
+            }
+            for outcome in Outcome::ALL {
+                assert_eq!(
+                    rules.score2(opponent as usize, outcome),
+                    score2(opponent, outcome)
+                );
+            }
+        }
+        assert_eq!(rules.score_guide(TEST_INPUT, Reading::Shape), Ok(15));
+        assert_eq!(rules.score_guide(TEST_INPUT, Reading::Outcome), Ok(12));
+    }
+
+    #[test]
+    fn test_variants() {
+        let rules = Rules::parse(RPSLS).unwrap();
+        assert_eq!(rules.shapes().len(), 5);
+        // Spock vaporizes rock, and lizard poisons Spock.
+        assert_eq!(rules.outcome(4, 0), Outcome::Win);
+        assert_eq!(rules.outcome(4, 3), Outcome::Loss);
+        // The test guide only uses the puzzle's shapes, which play the same as before in part 1.
+        assert_eq!(rules.score_guide(TEST_INPUT, Reading::Shape), Ok(15));
+        // To beat rock, Spock scores more than paper.
+        assert_eq!(rules.score2(0, Outcome::Win), 5 + 6);
+        // Lizard poisons Spock.
+        assert_eq!(rules.score_guide("E V\n", Reading::Shape), Ok(4 + 6));
+
+        let rules = Rules::parse(RPS7).unwrap();
+        assert_eq!(rules.shapes().len(), 7);
+        for i in 0..7 {
+            assert_eq!((i + 1..i + 4).filter(|j| rules.beats(i, j % 7)).count(), 3);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        let error = |input: &str| {
+            let e = Rules::parse(input).unwrap_err();
+            (e.line, e.column, e.message)
+        };
+        let standard = STANDARD.lines().collect::<Vec<_>>();
+        let replace = |from: &str, to: &str| standard.join("\n").replacen(from, to, 1);
+
+        let e = error(&replace("shape paper B Y 2", "shape paper A Y 2"));
+        assert_eq!((e.0, e.1), (7, 13));
+        let e = error(&replace("shape paper B Y 2", "shape paper BB Y 2"));
+        assert_eq!((e.0, e.1), (7, 13));
+        let e = error(&replace("paper beats rock", "paper beats stone"));
+        assert_eq!((e.0, e.1, e.2.as_str()), (11, 13, "unknown shape"));
+        let e = error(&replace("paper beats rock", "paper beats paper"));
+        assert_eq!((e.0, e.1), (11, 13));
+        let e = error(&replace("paper beats rock", "rock beats scissors"));
+        assert_eq!(
+            (e.0, e.1, e.2.as_str()),
+            (
+                7,
+                7,
+                "each shape must beat 1 of the others, but this one beats 0"
+            )
+        );
+        let e = error(&replace("paper beats rock", "scissors beats rock"));
+        assert_eq!((e.0, e.2.as_str()), (11, "rock already beats scissors"));
+        let e = error(&replace("draw Y 3", "draw Y"));
+        assert_eq!((e.0, e.1), (15, 7));
+        let e = error(&replace("draw Y 3", ""));
+        assert_eq!((e.0, e.2.as_str()), (17, "expected a \"draw\" rule"));
+        let e = error(&replace("shape scissors C Z 3", ""));
+        assert_eq!(e.2, "expected an odd number of shapes, at least 3, not 2");
+        let e = error(&replace("win Z 6", "win Z 1000001"));
+        assert_eq!(
+            (e.0, e.1, e.2.as_str()),
+            (16, 7, "expected a score of at most 1000000")
+        );
+        let e = error(&replace("shape rock A X 1", "shape rock A X 4294967295"));
+        assert_eq!((e.0, e.1), (6, 16));
+    }
+
+    #[test]
+    fn test_max_score() {
+        // Every shape and outcome scores as much as it can, over more rounds than a u32 total
+        // could hold.
+        let text = STANDARD
+            .replace(" 1\n", " 1000000\n")
+            .replace(" 2\n", " 1000000\n")
+            .replace(" 3\n", " 1000000\n")
+            .replace(" 0\n", " 1000000\n")
+            .replace(" 6\n", " 1000000\n");
+        let rules = Rules::parse(&text).unwrap();
+        assert_eq!(rules.score1(0, 1), 2 * MAX_SCORE);
+        let guide = "A Y\n".repeat(5000);
+        assert_eq!(
+            rules.score_guide(&guide, Reading::Shape),
+            Ok(5000 * 2 * MAX_SCORE as u64)
+        );
+    }
+
+    #[test]
+    fn test_invalid_guide() {
+        let rules = Rules::parse(RPSLS).unwrap();
+        let e = rules
+            .score_guide("A Y\nE V\n", Reading::Outcome)
+            .unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(e.message, "expected one of X, Y, Z");
+    }
+}
@@ -0,0 +1,318 @@
+// Tournaments between strategies, for `day02 simulate`. Instead of scoring a fixed guide, two
+// strategies play each other round after round, each seeing what the other played once a round
+// is over, and each is scored as the player is in part 1.
+
+use crate::{score1, Outcome, Round, Shape};
+use aoc_common::Rng;
+use std::fmt;
+
+pub trait Strategy {
+    // The shape to play in the next round.
+    fn play(&mut self) -> Shape;
+
+    // Learn what the opponent played in the round that just ended.
+    fn observe(&mut self, _opponent: Shape) {}
+}
+
+// The names of the strategies that `strategy` knows, with what they do.
+pub const STRATEGIES: [(&str, &str); 6] = [
+    (
+        "guide",
+        "replay the opponent's column of the guide (A, B and C)",
+    ),
+    (
+        "part1",
+        "replay the guide's second column, read as shapes as in part 1",
+    ),
+    (
+        "part2",
+        "replay the guide's second column, read as outcomes as in part 2",
+    ),
+    ("random", "play a random shape"),
+    (
+        "frequency",
+        "beat the shape the opponent has played the most",
+    ),
+    (
+        "markov",
+        "beat the shape the opponent has most often played after their last one",
+    ),
+];
+
+// Create the strategy called `name`. The replays go round the guide `rounds` again when they get
+// to the end, and `seed` seeds the random strategy.
+pub fn strategy(name: &str, rounds: &[Round], seed: u64) -> Result<Box<dyn Strategy>, String> {
+    let replay = |shapes: Vec<Shape>| -> Result<Box<dyn Strategy>, String> {
+        if shapes.is_empty() {
+            return Err(format!("{} needs a guide with at least one round", name));
+        }
+        Ok(Box::new(Replay { shapes, next: 0 }))
+    };
+    match name {
+        "guide" => replay(rounds.iter().map(|round| round.opponent).collect()),
+        "part1" => replay(rounds.iter().map(|round| round.column.shape()).collect()),
+        "part2" => replay(
+            rounds
+                .iter()
+                .map(|round| round.opponent.against(round.column.outcome()))
+                .collect(),
+        ),
+        "random" => Ok(Box::new(Random {
+            rng: Rng::new(seed),
+        })),
+        "frequency" => Ok(Box::new(Frequency { counts: [0; 3] })),
+        "markov" => Ok(Box::new(Markov {
+            transitions: [[0; 3]; 3],
+            last: None,
+        })),
+        _ => Err(format!("Unknown strategy: {}", name)),
+    }
+}
+
+// Play the same shapes over and over.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    shapes: Vec<Shape>,
+    next: usize,
+}
+
+impl Strategy for Replay {
+    fn play(&mut self) -> Shape {
+        let shape = self.shapes[self.next];
+        self.next = (self.next + 1) % self.shapes.len();
+        shape
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Random {
+    rng: Rng,
+}
+
+impl Strategy for Random {
+    fn play(&mut self) -> Shape {
+        *self.rng.choose(&Shape::ALL)
+    }
+}
+
+// The shape that is played the most according to `counts`, indexed by shape. Ties go to the
+// first of the shapes.
+fn most(counts: &[u64; 3]) -> Shape {
+    let mut best = 0;
+    for i in 1..3 {
+        if counts[i] > counts[best] {
+            best = i;
+        }
+    }
+    Shape::ALL[best]
+}
+
+#[derive(Debug, Clone)]
+pub struct Frequency {
+    counts: [u64; 3],
+}
+
+impl Strategy for Frequency {
+    fn play(&mut self) -> Shape {
+        most(&self.counts).beaten_by()
+    }
+
+    fn observe(&mut self, opponent: Shape) {
+        self.counts[opponent as usize] += 1;
+    }
+}
+
+// Predict the opponent's next shape from their last one, as a Markov chain of their shapes.
+#[derive(Debug, Clone)]
+pub struct Markov {
+    // `transitions[a][b]` is how many times the opponent played `b` straight after `a`.
+    transitions: [[u64; 3]; 3],
+    last: Option<Shape>,
+}
+
+impl Strategy for Markov {
+    fn play(&mut self) -> Shape {
+        match self.last {
+            Some(last) => most(&self.transitions[last as usize]).beaten_by(),
+            None => Shape::Rock,
+        }
+    }
+
+    fn observe(&mut self, opponent: Shape) {
+        if let Some(last) = self.last {
+            self.transitions[last as usize][opponent as usize] += 1;
+        }
+        self.last = Some(opponent);
+    }
+}
+
+// How one side of a tournament did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Record {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: u64,
+}
+
+impl Record {
+    fn add(&mut self, shape: Shape, opponent: Shape) {
+        match Outcome::of(shape, opponent) {
+            Outcome::Win => self.wins += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Loss => self.losses += 1,
+        }
+        self.score += score1(opponent, shape) as u64;
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rate = |n: usize| 100.0 * n as f64 / self.rounds().max(1) as f64;
+        write!(
+            f,
+            "wins {:.1}%, draws {:.1}%, losses {:.1}%, score {}",
+            rate(self.wins),
+            rate(self.draws),
+            rate(self.losses),
+            self.score
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tournament {
+    pub player: Record,
+    pub opponent: Record,
+}
+
+// Play `rounds` rounds between `player` and `opponent`.
+pub fn simulate(
+    player: &mut dyn Strategy,
+    opponent: &mut dyn Strategy,
+    rounds: usize,
+) -> Tournament {
+    let mut tournament = Tournament {
+        player: Record::default(),
+        opponent: Record::default(),
+    };
+    for _ in 0..rounds {
+        let (mine, theirs) = (player.play(), opponent.play());
+        tournament.player.add(mine, theirs);
+        tournament.opponent.add(theirs, mine);
+        player.observe(theirs);
+        opponent.observe(mine);
+    }
+    tournament
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+    use proptest::prelude::*;
+
+    const TEST_INPUT: &str = include_str!("../data/test.txt");
+
+    fn play(player: &str, opponent: &str, rounds: usize) -> Tournament {
+        let guide = parse_input(TEST_INPUT).unwrap();
+        simulate(
+            strategy(player, &guide, 1).unwrap().as_mut(),
+            strategy(opponent, &guide, 2).unwrap().as_mut(),
+            rounds,
+        )
+    }
+
+    #[test]
+    fn test_replay() {
+        // Replaying the guide scores the same as the two parts.
+        assert_eq!(play("part1", "guide", 3).player.score, 15);
+        assert_eq!(play("part2", "guide", 3).player.score, 12);
+        // Going round the guide again.
+        let tournament = play("part1", "guide", 7);
+        assert_eq!(tournament.player.score, 15 * 2 + 8);
+        assert_eq!(
+            tournament.player,
+            Record {
+                wins: 3,
+                draws: 2,
+                losses: 2,
+                score: 38
+            }
+        );
+        assert_eq!(tournament.opponent.wins, tournament.player.losses);
+    }
+
+    #[test]
+    fn test_adaptive() {
+        // The guide's opponent plays rock, paper, scissors over and over, which the Markov
+        // predictor learns after seeing it once.
+        let tournament = play("markov", "guide", 300);
+        assert!(tournament.player.losses <= 3);
+        // Whereas counting the shapes can't tell which comes next.
+        let tournament = play("frequency", "guide", 300);
+        assert!(tournament.player.wins < 200);
+        // Against a constant shape, counting wins every round after the first.
+        let mut rock = Replay {
+            shapes: vec![Shape::Rock],
+            next: 0,
+        };
+        let mut frequency = strategy("frequency", &[], 0).unwrap();
+        let tournament = simulate(frequency.as_mut(), &mut rock, 100);
+        assert_eq!(tournament.player.wins, 100);
+    }
+
+    #[test]
+    fn test_random() {
+        let tournament = play("random", "random", 3000);
+        for n in [
+            tournament.player.wins,
+            tournament.player.draws,
+            tournament.player.losses,
+        ] {
+            assert!((800..1200).contains(&n));
+        }
+        assert_eq!(tournament, play("random", "random", 3000));
+    }
+
+    #[test]
+    fn test_strategy() {
+        assert!(strategy("guide", &[], 1).is_err());
+        assert!(strategy("markov", &[], 1).is_ok());
+        assert!(strategy("psychic", &[], 1).is_err());
+        for (name, _) in STRATEGIES {
+            let guide = parse_input(TEST_INPUT).unwrap();
+            assert!(strategy(name, &guide, 1).is_ok());
+        }
+        assert_eq!(
+            Record {
+                wins: 1,
+                draws: 2,
+                losses: 1,
+                score: 14
+            }
+            .to_string(),
+            "wins 25.0%, draws 50.0%, losses 25.0%, score 14"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_zero_sum(
+            player in prop::sample::select(STRATEGIES.map(|(name, _)| name).to_vec()),
+            opponent in prop::sample::select(STRATEGIES.map(|(name, _)| name).to_vec()),
+            rounds in 0..100usize,
+        ) {
+            let t = play(player, opponent, rounds);
+            prop_assert_eq!(t.player.rounds(), rounds);
+            prop_assert_eq!(
+                (t.player.wins, t.player.draws, t.player.losses),
+                (t.opponent.losses, t.opponent.draws, t.opponent.wins)
+            );
+        }
+    }
+}
@@ -0,0 +1,76 @@
+// Synthetic inputs: `size` rucksacks (rounded up to a whole number of groups of three).
+//
+// Each rucksack has exactly one item type in both compartments, and each group of three has exactly
+// one item type in common (the badge). To guarantee the latter, the item types other than the
+// badge are dealt out to the three rucksacks of a group, so that no other type can be shared by
+// all three.
+
+use crate::Day03;
+use aoc_common::{Generate, Rng};
+
+const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// A rucksack made from the item types in `pool`, which must include the badge.
+fn rucksack(rng: &mut Rng, pool: &[char], badge: char) -> String {
+    let duplicate = *rng.choose(pool);
+    let mut others: Vec<char> = pool.iter().copied().filter(|&c| c != duplicate).collect();
+    rng.shuffle(&mut others);
+    let (first, second) = others.split_at(others.len() / 2);
+
+    let half = rng.below(12) + 4;
+    let mut compartments = [vec![duplicate], vec![duplicate]];
+    for (compartment, types) in compartments.iter_mut().zip([first, second]) {
+        while compartment.len() < half {
+            compartment.push(*rng.choose(types));
+        }
+    }
+    // Make sure the badge is in there somewhere.
+    if duplicate != badge {
+        let side = usize::from(second.contains(&badge));
+        compartments[side][1] = badge;
+    }
+    for compartment in compartments.iter_mut() {
+        rng.shuffle(compartment);
+    }
+    compartments.concat().into_iter().collect()
+}
+
+impl Generate for Day03 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut input = String::new();
+        for _ in 0..size.div_ceil(3).max(1) {
+            let mut items: Vec<char> = ITEMS.chars().collect();
+            rng.shuffle(&mut items);
+            let badge = items.pop().unwrap();
+            for pool in items.chunks(items.len() / 3) {
+                let mut pool = pool.to_vec();
+                pool.push(badge);
+                input.push_str(&rucksack(rng, &pool, badge));
+                input.push('\n');
+            }
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_duplicate, get_badge};
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_generate() {
+        let input = Day03::generate(&mut Rng::new(1), 299);
+        assert_eq!(input, Day03::generate(&mut Rng::new(1), 299));
+        let rucksacks = Day03::parse(&input).unwrap();
+        assert_eq!(rucksacks.len(), 300);
+        for group in rucksacks.chunks(3) {
+            for rucksack in group {
+                assert!(find_duplicate(rucksack).is_some());
+            }
+            let group: Vec<&str> = group.iter().map(String::as_str).collect();
+            assert!(get_badge(&group).is_some());
+        }
+    }
+}
@@ -0,0 +1,280 @@
+// Advent of Code 2022 - Day 3
+// Find duplicate items in rucksacks
+
+mod generate;
+
+use aoc_common::stream::{self, StreamError};
+use aoc_common::{ParseError, Solver, StreamSolver};
+use std::collections::HashSet;
+use std::io::BufRead;
+
+// Given a string representing the content of two backpacks, find the character that appears in
+// both the first and the second half of the string. If there is no such character, return None.
+fn find_duplicate(rucksack: &str) -> Option<char> {
+    let mut seen = HashSet::new();
+
+    // Split the input string into two halves
+    let (first, second) = rucksack.split_at(rucksack.len() / 2);
+
+    // Change the first half into a HashSet
+    for c in first.chars() {
+        seen.insert(c);
+    }
+
+    // Check if any of the characters in the second half are in the HashSet
+    second.chars().find(|c| seen.contains(c))
+}
+
+// The priority of a char is 1 to 27 for 'a' to 'z', and 28 to 54 for 'A' to 'Z'.
+fn priority(c: char) -> u32 {
+    if c.is_ascii_lowercase() {
+        c as u32 - 'a' as u32 + 1
+    } else {
+        c as u32 - 'A' as u32 + 27
+    }
+}
+
+// Given a vector of strings, find the character common to each string.
+// If there is more than one such character, or no such character, return None.
+fn get_badge(rucksacks: &[&str]) -> Option<char> {
+    let mut items: Vec<HashSet<char>> = Vec::new();
+
+    // For each line in the input string, turn the line into a HashSet and add it to the `items`
+    // vector.
+    for rucksack in rucksacks {
+        let mut seen = HashSet::new();
+        for c in rucksack.chars() {
+            seen.insert(c);
+        }
+        items.push(seen);
+    }
+
+    // Calculate the intersection of all the HashSets in the `items` vector.
+    let mut intersection = items[0].clone();
+    for item in items.iter().skip(1) {
+        intersection = intersection.intersection(item).cloned().collect();
+    }
+
+    // If there is exactly one character in the intersection, return it.
+    // Otherwise, return None.
+    if intersection.len() == 1 {
+        Some(*intersection.iter().next().unwrap())
+    } else {
+        None
+    }
+}
+
+// Check that a rucksack contains only items (letters), and can be split evenly into two
+// compartments.
+fn check_rucksack(rucksack: &str) -> Result<(), ParseError> {
+    if let Some((i, c)) = rucksack
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii_alphabetic())
+    {
+        return Err(ParseError::new(
+            rucksack,
+            &rucksack[i..i + c.len_utf8()],
+            "expected an item (a-z or A-Z)",
+        ));
+    }
+    if !rucksack.len().is_multiple_of(2) {
+        return Err(ParseError::new(
+            rucksack,
+            rucksack,
+            "expected an even number of items",
+        ));
+    }
+    Ok(())
+}
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    const DAY: u32 = 3;
+
+    // The contents of each rucksack, one per line.
+    type Input = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                check_rucksack(line)
+                    .map(|_| line.to_string())
+                    .map_err(|e| e.at_line(i + 1))
+            })
+            .collect()
+    }
+
+    // Part 1: calculate the priority of the duplicate item in each rucksack, and accumulate the
+    // sum of the results.
+    fn part1(rucksacks: &Self::Input) -> u32 {
+        rucksacks
+            .iter()
+            .map(|rucksack| find_duplicate(rucksack).expect("No duplicate found"))
+            .map(priority)
+            .sum()
+    }
+
+    // Part 2: take the rucksacks 3 at a time, and pass them to the `get_badge` function to find
+    // the common item ("badge"). For each badge, calculate the priority and accumulate the sum of
+    // the results.
+    fn part2(rucksacks: &Self::Input) -> u32 {
+        rucksacks
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .chunks(3)
+            .map(|chunk| get_badge(chunk).expect("No badge found"))
+            .map(priority)
+            .sum()
+    }
+}
+
+impl StreamSolver for Day03 {
+    // Only the current group of three rucksacks is kept.
+    fn solve_stream<R: BufRead>(reader: R) -> Result<(u32, u32), StreamError> {
+        let (mut sum1, mut sum2) = (0, 0);
+        let mut group: Vec<String> = Vec::with_capacity(3);
+        let mut lines = 0;
+        // Add the badge of the group that ends at line `number`.
+        let mut add_badge = |group: &mut Vec<String>, number: usize| -> Result<(), StreamError> {
+            let rucksacks: Vec<&str> = group.iter().map(String::as_str).collect();
+            let badge = get_badge(&rucksacks).ok_or_else(|| {
+                StreamError::NoAnswer(format!("the group ending at line {} has no badge", number))
+            })?;
+            sum2 += priority(badge);
+            group.clear();
+            Ok(())
+        };
+        stream::for_each_line(reader, |number, line| {
+            check_rucksack(line)?;
+            let duplicate = find_duplicate(line).ok_or_else(|| {
+                StreamError::NoAnswer(format!(
+                    "rucksack {} has no item in both compartments",
+                    number
+                ))
+            })?;
+            sum1 += priority(duplicate);
+            lines = number;
+            group.push(line.to_string());
+            if group.len() == 3 {
+                add_badge(&mut group, number)?;
+            }
+            Ok(())
+        })?;
+        if !group.is_empty() {
+            add_badge(&mut group, lines)?;
+        }
+        Ok((sum1, sum2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{Generate, Rng};
+    use proptest::prelude::*;
+
+    // Create a static vector that represents the test rucksacks and their duplicate items.
+    const TEST_DATA: &[(&str, char)] = &[
+        ("vJrwpWtwJgWrhcsFMMfFFhFp", 'p'),
+        ("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL", 'L'),
+        ("PmmdzqPrVvPwwTWBwg", 'P'),
+        ("wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn", 'v'),
+        ("ttgJtRGJQctTZtZT", 't'),
+        ("CrZsJsPPZsGzwwsLwLmpwMDw", 's'),
+    ];
+
+    #[test]
+    fn test_find_duplicate() {
+        for (rucksack, duplicate) in TEST_DATA {
+            assert_eq!(find_duplicate(rucksack), Some(*duplicate));
+        }
+    }
+
+    #[test]
+    fn test_priority() {
+        assert_eq!(priority('a'), 1);
+        assert_eq!(priority('z'), 26);
+        assert_eq!(priority('A'), 27);
+        assert_eq!(priority('Z'), 52);
+    }
+
+    #[test]
+    fn test_get_badges() {
+        // Collect the first 3 lines of the test data into a vector, and get those lines' badge.
+        let rucksacks: Vec<&str> = TEST_DATA
+            .iter()
+            .map(|(rucksack, _)| *rucksack)
+            .take(3)
+            .collect();
+        assert_eq!(get_badge(&rucksacks), Some('r'));
+        // Do the same for the next 3 lines.
+        let rucksacks: Vec<&str> = TEST_DATA
+            .iter()
+            .map(|(rucksack, _)| *rucksack)
+            .skip(3)
+            .take(3)
+            .collect();
+        assert_eq!(get_badge(&rucksacks), Some('Z'));
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = Day03::generate(&mut Rng::new(3), 3000);
+        let rucksacks = Day03::parse(&input).unwrap();
+        assert_eq!(
+            Day03::solve_stream(input.as_bytes()).unwrap(),
+            (Day03::part1(&rucksacks), Day03::part2(&rucksacks))
+        );
+        let e = Day03::solve_stream("aa\nabc\n".as_bytes()).unwrap_err();
+        assert!(matches!(e, StreamError::Parse(e) if e.line == 2));
+        // Rucksacks that are well formed, but have no answer.
+        let e = Day03::solve_stream("aa\nab\n".as_bytes()).unwrap_err();
+        assert_eq!(e.to_string(), "rucksack 2 has no item in both compartments");
+        let e = Day03::solve_stream("aa\nbb\ncc\ndd\n".as_bytes()).unwrap_err();
+        assert_eq!(e.to_string(), "the group ending at line 3 has no badge");
+    }
+
+    fn rucksacks() -> impl Strategy<Value = Vec<String>> {
+        prop::collection::vec(
+            ("[a-zA-Z]{1,20}", "[a-zA-Z]{20}").prop_map(|(first, second)| {
+                // Both compartments hold the same number of items.
+                format!("{}{}", first, &second[..first.len()])
+            }),
+            1..30,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(rucksacks in rucksacks()) {
+            let text: String = rucksacks.iter().map(|r| format!("{}\n", r)).collect();
+            prop_assert_eq!(Day03::parse(&text).unwrap(), rucksacks);
+        }
+
+        #[test]
+        fn test_invalid_rucksack(
+            mut rucksacks in rucksacks(),
+            line in any::<prop::sample::Index>(),
+            position in any::<prop::sample::Index>(),
+            junk in "[a-zA-Z0-9 .]",
+        ) {
+            // Insert a single character, which either isn't an item or leaves an odd number.
+            let i = line.index(rucksacks.len());
+            let at = position.index(rucksacks[i].len() + 1);
+            rucksacks[i].insert_str(at, &junk);
+            let e = Day03::parse(&rucksacks.join("\n")).unwrap_err();
+            prop_assert_eq!(e.line, i + 1);
+        }
+
+        #[test]
+        fn test_no_panic(input in "[a-zA-Z0-9\n]{0,100}") {
+            let _ = Day03::parse(&input);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// Synthetic inputs: `size` pairs of section assignments.
+
+use crate::Day04;
+use aoc_common::{Generate, Rng};
+
+impl Generate for Day04 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            let mut range = || {
+                let start = rng.between(1, 99);
+                (start, rng.between(start, 99))
+            };
+            let ((a, b), (c, d)) = (range(), range());
+            input.push_str(&format!("{}-{},{}-{}\n", a, b, c, d));
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_generate() {
+        let input = Day04::generate(&mut Rng::new(1), 100);
+        assert_eq!(input, Day04::generate(&mut Rng::new(1), 100));
+        let assignments = Day04::parse(&input).unwrap();
+        assert_eq!(assignments.len(), 100);
+        // Every pair that contains the other also overlaps it.
+        assert!(Day04::part1(&assignments) <= Day04::part2(&assignments));
+    }
+}
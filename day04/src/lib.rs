@@ -0,0 +1,206 @@
+// Advent of Code 2022 - Day 4
+// Find overlapping work assignments.
+
+mod generate;
+
+use aoc_common::error::parse_field;
+use aoc_common::stream::{self, StreamError};
+use aoc_common::{ParseError, Solver, StreamSolver};
+use std::io::BufRead;
+
+pub type Assignment = ((u32, u32), (u32, u32));
+
+// Whether one range in the pair is completely contained in the other.
+fn is_subset(&((a, b), (c, d)): &Assignment) -> bool {
+    ((a >= c) && (b <= d)) || ((c >= a) && (d <= b))
+}
+
+// Whether the ranges in the pair overlap at all.
+fn is_overlap(&((a, b), (c, d)): &Assignment) -> bool {
+    ((a >= c) && (a <= d))
+        || ((b >= c) && (b <= d))
+        || ((c >= a) && (c <= b))
+        || ((d >= a) && (d <= b))
+}
+
+// Find the assignment indices where one range in the pair is completely contained in the other.
+fn find_subsets(assignments: &[Assignment]) -> Vec<usize> {
+    (0..assignments.len())
+        .filter(|&i| is_subset(&assignments[i]))
+        .collect()
+}
+
+// Find the assignment indices where the ranges overlap at all.
+fn find_overlaps(assignments: &[Assignment]) -> Vec<usize> {
+    (0..assignments.len())
+        .filter(|&i| is_overlap(&assignments[i]))
+        .collect()
+}
+
+// Parse a string of the form "10-19,3-11" into a tuple of the form ((10, 19), (3, 11)).
+fn parse_assignment(assignment: &str) -> Result<Assignment, ParseError> {
+    let (first, second) = assignment.split_once(',').ok_or_else(|| {
+        ParseError::new(
+            assignment,
+            assignment,
+            "expected two ranges separated by ','",
+        )
+    })?;
+    Ok((
+        parse_range(assignment, first)?,
+        parse_range(assignment, second)?,
+    ))
+}
+
+// Parse a range of the form "10-19", which is part of the line `assignment`.
+fn parse_range(assignment: &str, range: &str) -> Result<(u32, u32), ParseError> {
+    let (start, end) = range
+        .split_once('-')
+        .ok_or_else(|| ParseError::new(assignment, range, "expected a range such as 2-4"))?;
+    let start = parse_field(assignment, start, "a section number")?;
+    let end = parse_field(assignment, end, "a section number")?;
+    if end < start {
+        return Err(ParseError::new(
+            assignment,
+            range,
+            "expected the range to end after it starts",
+        ));
+    }
+    Ok((start, end))
+}
+
+// Read the input into a vector of tuple pairs.
+pub fn parse_input(input: &str) -> Result<Vec<Assignment>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_assignment(line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    const DAY: u32 = 4;
+
+    type Input = Vec<Assignment>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    // Part 1: the number of pairs where one range fully contains the other.
+    fn part1(assignments: &Self::Input) -> usize {
+        find_subsets(assignments).len()
+    }
+
+    // Part 2: the number of pairs where the ranges overlap at all.
+    fn part2(assignments: &Self::Input) -> usize {
+        find_overlaps(assignments).len()
+    }
+}
+
+impl StreamSolver for Day04 {
+    fn solve_stream<R: BufRead>(reader: R) -> Result<(usize, usize), StreamError> {
+        let (mut subsets, mut overlaps) = (0, 0);
+        stream::for_each_line(reader, |_, line| {
+            let assignment = parse_assignment(line)?;
+            subsets += is_subset(&assignment) as usize;
+            overlaps += is_overlap(&assignment) as usize;
+            Ok(())
+        })?;
+        Ok((subsets, overlaps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{Generate, Rng};
+    use proptest::prelude::*;
+
+    const TEST_INPUT: &[Assignment] = &[
+        ((2, 4), (6, 8)),
+        ((2, 3), (4, 5)),
+        ((5, 7), (7, 9)),
+        ((2, 8), (3, 7)),
+        ((6, 6), (4, 6)),
+        ((2, 6), (4, 8)),
+    ];
+
+    #[test]
+    fn test_find_subsets() {
+        assert_eq!(find_subsets(TEST_INPUT), vec![3, 4]);
+    }
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(parse_assignment("2-14,16-108"), Ok(((2, 14), (16, 108))));
+        let e = parse_assignment("2-14,16-x").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (9, "x"));
+        let e = parse_assignment("2-14").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (1, "2-14"));
+        let e = parse_assignment("2-14,16").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (6, "16"));
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = Day04::generate(&mut Rng::new(4), 3000);
+        let assignments = parse_input(&input).unwrap();
+        assert_eq!(
+            Day04::solve_stream(input.as_bytes()).unwrap(),
+            (Day04::part1(&assignments), Day04::part2(&assignments))
+        );
+        let e = Day04::solve_stream("2-4,6-8\n2-3,5-4\n".as_bytes()).unwrap_err();
+        assert!(matches!(e, StreamError::Parse(e) if e.line == 2));
+    }
+
+    fn range() -> impl Strategy<Value = (u32, u32)> {
+        (0..100u32, 0..100u32).prop_map(|(start, len)| (start, start + len))
+    }
+
+    fn render(assignments: &[Assignment]) -> String {
+        assignments
+            .iter()
+            .map(|((a, b), (c, d))| format!("{}-{},{}-{}\n", a, b, c, d))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(assignments in prop::collection::vec((range(), range()), 1..50)) {
+            prop_assert_eq!(parse_input(&render(&assignments)).unwrap(), assignments);
+        }
+
+        #[test]
+        fn test_reversed_range(start in 1..100u32, len in 1..100u32) {
+            let line = format!("{}-{},1-2", start + len, start);
+            let e = parse_assignment(&line).unwrap_err();
+            prop_assert_eq!(e.column, 1);
+        }
+
+        #[test]
+        fn test_invalid_separator(
+            assignments in prop::collection::vec((range(), range()), 1..50),
+            line in any::<prop::sample::Index>(),
+            separator in 0..3usize,
+            junk in "[ .;:_]",
+        ) {
+            // Replace one of the separators on a line.
+            let i = line.index(assignments.len());
+            let mut lines: Vec<String> = render(&assignments).lines().map(String::from).collect();
+            let (at, _) = lines[i].match_indices(['-', ',']).nth(separator).unwrap();
+            lines[i].replace_range(at..at + 1, &junk);
+            let e = parse_input(&lines.join("\n")).unwrap_err();
+            prop_assert_eq!(e.line, i + 1);
+        }
+
+        #[test]
+        fn test_no_panic(input in "[0-9,\\- \n]{0,80}") {
+            let _ = parse_input(&input);
+        }
+    }
+}
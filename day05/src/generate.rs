@@ -0,0 +1,68 @@
+// Synthetic inputs: nine stacks of crates, and `size` moves between them.
+//
+// The moves are checked against the stack heights as they are generated, and never take the last
+// crate from a stack, so that every stack still has a crate on top at the end.
+
+use crate::Day05;
+use aoc_common::{Generate, Rng};
+
+const NUM_STACKS: usize = 9;
+
+impl Generate for Day05 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut heights: Vec<usize> = (0..NUM_STACKS).map(|_| rng.below(7) + 2).collect();
+        let mut input = String::new();
+
+        // Draw the stacks from the top down, followed by the stack numbers.
+        let top = *heights.iter().max().unwrap();
+        for level in (0..top).rev() {
+            let row: Vec<String> = heights
+                .iter()
+                .map(|&h| {
+                    if level < h {
+                        format!("[{}]", (b'A' + rng.below(26) as u8) as char)
+                    } else {
+                        String::from("   ")
+                    }
+                })
+                .collect();
+            input.push_str(&row.join(" "));
+            input.push('\n');
+        }
+        let numbers: Vec<String> = (1..=NUM_STACKS).map(|n| format!(" {} ", n)).collect();
+        input.push_str(&numbers.join(" "));
+        input.push_str("\n\n");
+
+        for _ in 0..size {
+            let candidates: Vec<usize> = (0..NUM_STACKS).filter(|&s| heights[s] > 1).collect();
+            let src = *rng.choose(&candidates);
+            let mut dst = rng.below(NUM_STACKS - 1);
+            if dst >= src {
+                dst += 1;
+            }
+            let qty = rng.below(heights[src] - 1) + 1;
+            heights[src] -= qty;
+            heights[dst] += qty;
+            input.push_str(&format!("move {} from {} to {}\n", qty, src + 1, dst + 1));
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_generate() {
+        let input = Day05::generate(&mut Rng::new(1), 500);
+        assert_eq!(input, Day05::generate(&mut Rng::new(1), 500));
+        let (stack, moves) = Day05::parse(&input).unwrap();
+        assert_eq!(stack.len(), NUM_STACKS);
+        assert_eq!(moves.len(), 500);
+        let model = (stack, moves);
+        assert_eq!(Day05::part1(&model).len(), NUM_STACKS);
+        assert_eq!(Day05::part2(&model).len(), NUM_STACKS);
+    }
+}
@@ -0,0 +1,393 @@
+// Advent of Code 2022 - Day 5
+// Shuffling crates
+
+mod generate;
+mod visual;
+
+use aoc_common::error::{next_field, parse_field};
+use aoc_common::{ParseError, Solver};
+
+pub type Stack = Vec<Vec<char>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    qty: u32,
+    src: usize,
+    dst: usize,
+}
+
+// Parse a move of the form "move 3 from 7 to 9" between the `num_stacks` stacks, converting the
+// stack numbers to 0-based indices.
+fn parse_move(line: &str, num_stacks: usize) -> Result<Move, ParseError> {
+    let mut words = line.split_whitespace();
+    parse_keyword(line, &mut words, "move")?;
+    let qty = parse_field(
+        line,
+        next_field(line, &mut words, "a quantity")?,
+        "a quantity",
+    )?;
+    parse_keyword(line, &mut words, "from")?;
+    let src = parse_stack(
+        line,
+        next_field(line, &mut words, "a stack number")?,
+        num_stacks,
+    )?;
+    parse_keyword(line, &mut words, "to")?;
+    let dst = parse_stack(
+        line,
+        next_field(line, &mut words, "a stack number")?,
+        num_stacks,
+    )?;
+    if let Some(word) = words.next() {
+        return Err(ParseError::new(line, word, "expected end of line"));
+    }
+    Ok(Move { qty, src, dst })
+}
+
+// Check that the next word in `line` is the keyword `expected`.
+fn parse_keyword<'a, I: Iterator<Item = &'a str>>(
+    line: &str,
+    words: &mut I,
+    expected: &str,
+) -> Result<(), ParseError> {
+    let word = next_field(line, words, &format!("'{}'", expected))?;
+    if word != expected {
+        return Err(ParseError::new(
+            line,
+            word,
+            format!("expected '{}'", expected),
+        ));
+    }
+    Ok(())
+}
+
+// Parse a 1-based stack number, and return it as an index into the stacks.
+fn parse_stack(line: &str, token: &str, num_stacks: usize) -> Result<usize, ParseError> {
+    let n: usize = parse_field(line, token, "a stack number")?;
+    if n == 0 || n > num_stacks {
+        return Err(ParseError::new(
+            line,
+            token,
+            format!("expected a stack number from 1 to {}", num_stacks),
+        ));
+    }
+    Ok(n - 1)
+}
+
+// Parse the specified puzzle input, and return a `Stack` containter with the initial state of the
+// stacks, and a `Vec` of `Move`s that describe the shuffling process.
+pub fn parse_input(input: &str) -> Result<(Stack, Vec<Move>), ParseError> {
+    // This 2D vector of characters initially represents the first part of the input file, which
+    // describes the initial state of the stacks.
+    let mut raw_stack: Vec<Vec<char>> = Vec::new();
+    // This will eventually store the moves between stacks, along with their lines.
+    let mut moves = Vec::new();
+    let mut move_lines = Vec::new();
+    let mut num_stacks = 0;
+
+    let mut file_part = 0;
+
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            file_part += 1;
+            continue;
+        }
+
+        if file_part == 0 {
+            // This is the first part of the input file, which describes the initial state of the
+            // stacks. Each stack takes up four columns, except the last which may lack the
+            // trailing space, or even the closing bracket. The crate is in the second column.
+            num_stacks = num_stacks.max((line.chars().count() + 2) / 4);
+            raw_stack.push(Vec::new());
+            for (j, c) in line.chars().enumerate() {
+                if j % 4 == 1 {
+                    raw_stack[i].push(c);
+                }
+            }
+        } else {
+            // The second part of the file describe moves in the form "move 3 from 7 to 9". Here we
+            // read each line, pack it into a `Move` struct, and append it to the `moves` vector.
+            moves.push(parse_move(line, num_stacks).map_err(|e| e.at_line(i + 1))?);
+            move_lines.push((i + 1, line));
+        }
+    }
+
+    // The drawing ends with a line of stack numbers, so it needs at least that line.
+    if raw_stack.is_empty() {
+        return Err(ParseError::missing("", "expected a drawing of the stacks").at_line(1));
+    }
+
+    // This will eventually store the initial state of the stacks.
+    let mut stack: Stack = vec![Vec::new(); num_stacks];
+
+    // Convert the `raw_stack` vector into a `Stack` container. This is done by iterating over
+    // `raw_stack` from next-to-last to first, and pushing each non-space character to the
+    // corresponding `stack` vector.
+    for i in (0..raw_stack.len() - 1).rev() {
+        for (j, c) in raw_stack[i].iter().enumerate() {
+            if *c != ' ' {
+                stack[j].push(*c);
+            }
+        }
+    }
+
+    // Check that each move takes no more crates than its stack holds at that point, so that
+    // shuffling can't run out of crates.
+    let mut heights: Vec<usize> = stack.iter().map(Vec::len).collect();
+    for (m, (number, line)) in moves.iter().zip(move_lines) {
+        let qty = m.qty as usize;
+        if qty > heights[m.src] {
+            let token = line.split_whitespace().nth(1).unwrap();
+            return Err(ParseError::new(
+                line,
+                token,
+                format!("stack {} only holds {} crates", m.src + 1, heights[m.src]),
+            )
+            .at_line(number));
+        }
+        heights[m.src] -= qty;
+        heights[m.dst] += qty;
+    }
+
+    Ok((stack, moves))
+}
+
+// Perform the shuffling process described by the `moves` vector, and return the final state of the
+// stacks, as performed by the CrateMover 9000.
+pub fn shuffle_9000(stack: &mut Stack, moves: &[Move]) {
+    for m in moves {
+        move_9000(stack, m);
+    }
+}
+
+// The CrateMover 9000 moves one crate at a time, so a move reverses the order of the crates.
+fn move_9000(stack: &mut Stack, m: &Move) {
+    let Move { qty, src, dst } = *m;
+    for _ in 0..qty {
+        let c = stack[src].pop().unwrap();
+        stack[dst].push(c);
+    }
+}
+
+// An alternative shuffling process, as performed by the CrateMover 9001.
+pub fn shuffle_9001(stack: &mut Stack, moves: &[Move]) {
+    for m in moves {
+        move_9001(stack, m);
+    }
+}
+
+// The CrateMover 9001 moves several crates at once, keeping their order.
+fn move_9001(stack: &mut Stack, m: &Move) {
+    let Move { qty, src, dst } = *m;
+    let mut tmp = Vec::new();
+    for _ in 0..qty {
+        let c = stack[src].pop().unwrap();
+        tmp.push(c);
+    }
+    for _ in 0..qty {
+        let c = tmp.pop().unwrap();
+        stack[dst].push(c);
+    }
+}
+
+// The crates on top of each stack, read from left to right. Stacks that are empty have nothing to
+// add.
+fn top_crates(stack: &Stack) -> String {
+    stack.iter().filter_map(|s| s.last()).collect()
+}
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    const DAY: u32 = 5;
+
+    type Input = (Stack, Vec<Move>);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    // Part 1: the top crates after shuffling with the CrateMover 9000.
+    fn part1((stack, moves): &Self::Input) -> String {
+        let mut stack = stack.clone();
+        shuffle_9000(&mut stack, moves);
+        top_crates(&stack)
+    }
+
+    // Part 2: the top crates after shuffling with the CrateMover 9001.
+    fn part2((stack, moves): &Self::Input) -> String {
+        let mut stack = stack.clone();
+        shuffle_9001(&mut stack, moves);
+        top_crates(&stack)
+    }
+}
+
+// Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const TEST_INPUT: &str = include_str!("../data/test.txt");
+
+    #[test]
+    fn test_parse_input() {
+        let (stack, moves) = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(stack.len(), 3);
+        assert_eq!(stack[0].len(), 2);
+        assert_eq!(stack[1].len(), 3);
+        assert_eq!(stack[2].len(), 1);
+        assert_eq!(moves.len(), 4);
+
+        assert_eq!(stack[0][0], 'Z');
+        assert_eq!(stack[1][2], 'D');
+        assert_eq!(stack[2][0], 'P');
+
+        assert_eq!(moves[0].qty, 1);
+        assert_eq!(moves[1].src, 0);
+        assert_eq!(moves[3].dst, 1);
+    }
+
+    #[test]
+    fn test_shuffle_9000() {
+        let (mut stack, moves) = parse_input(TEST_INPUT).unwrap();
+        shuffle_9000(&mut stack, &moves);
+        assert_eq!(stack[0][0], 'C');
+        assert_eq!(stack[1][0], 'M');
+        assert_eq!(stack[2][3], 'Z');
+    }
+
+    #[test]
+    fn test_shuffle_9001() {
+        let (mut stack, moves) = parse_input(TEST_INPUT).unwrap();
+        shuffle_9001(&mut stack, &moves);
+        assert_eq!(stack[0][0], 'M');
+        assert_eq!(stack[1][0], 'C');
+        assert_eq!(stack[2][3], 'D');
+    }
+
+    #[test]
+    fn test_parse_move() {
+        let m = parse_move("move 3 from 1 to 2", 3).unwrap();
+        assert_eq!((m.qty, m.src, m.dst), (3, 0, 1));
+        let e = parse_move("move 3 from 4 to 2", 3).unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (13, "4"));
+        let e = parse_move("move 3 form 1 to 2", 3).unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (8, "form"));
+        let e = parse_move("move 3 from 1", 3).unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (14, ""));
+    }
+
+    #[test]
+    fn test_too_many_crates() {
+        let input = TEST_INPUT.replace("move 3 from 1 to 3", "move 5 from 1 to 3");
+        let e = parse_input(&input).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (7, 6, "5"));
+    }
+
+    #[test]
+    fn test_empty_stacks() {
+        // Emptied by a move, and empty from the start.
+        let input = parse_input("[A]    \n 1   2 \n\nmove 1 from 1 to 2\n").unwrap();
+        assert_eq!(Day05::part1(&input), "A");
+        let input = parse_input("    [B]\n 1   2 \n").unwrap();
+        assert_eq!(Day05::part2(&input), "B");
+    }
+
+    // Draw the stacks as in the puzzle input, followed by the moves.
+    fn render(stack: &Stack, moves: &[Move]) -> String {
+        let mut text = String::new();
+        let top = stack.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..top).rev() {
+            let row: Vec<String> = stack
+                .iter()
+                .map(|s| match s.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => String::from("   "),
+                })
+                .collect();
+            text.push_str(&row.join(" "));
+            text.push('\n');
+        }
+        let numbers: Vec<String> = (1..=stack.len()).map(|n| format!(" {} ", n)).collect();
+        text.push_str(&numbers.join(" "));
+        text.push_str("\n\n");
+        for m in moves {
+            text.push_str(&format!(
+                "move {} from {} to {}\n",
+                m.qty,
+                m.src + 1,
+                m.dst + 1
+            ));
+        }
+        text
+    }
+
+    // Stacks of crates, and moves between them that never take more crates than a stack holds.
+    fn model() -> impl Strategy<Value = (Stack, Vec<Move>)> {
+        let stacks = prop::collection::vec(
+            prop::collection::vec(prop::char::range('A', 'Z'), 0..8),
+            1..10,
+        );
+        let moves = prop::collection::vec((any::<u32>(), any::<usize>(), any::<usize>()), 0..30);
+        (stacks, moves).prop_map(|(stack, raw)| {
+            let n = stack.len();
+            let mut heights: Vec<usize> = stack.iter().map(Vec::len).collect();
+            let moves = raw
+                .into_iter()
+                .map(|(qty, src, dst)| {
+                    let (src, dst) = (src % n, dst % n);
+                    let qty = qty % (heights[src] as u32 + 1);
+                    heights[src] -= qty as usize;
+                    heights[dst] += qty as usize;
+                    Move { qty, src, dst }
+                })
+                .collect();
+            (stack, moves)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip((stack, moves) in model()) {
+            let (parsed_stack, parsed_moves) = parse_input(&render(&stack, &moves)).unwrap();
+            prop_assert_eq!(parsed_stack, stack);
+            prop_assert_eq!(parsed_moves, moves);
+        }
+
+        #[test]
+        fn test_parts((stack, moves) in model()) {
+            // Moves only shift crates around, so there are as many on top at the end as there
+            // are stacks left holding any.
+            let input = (stack, moves);
+            for top in [Day05::part1(&input), Day05::part2(&input)] {
+                prop_assert!(top.len() <= input.0.len());
+                prop_assert!(top.chars().all(|c| c.is_ascii_uppercase()));
+            }
+        }
+
+        #[test]
+        fn test_invalid_move(
+            (stack, moves) in model(),
+            junk in prop_oneof![
+                Just(String::from("move 1 from 0 to 1")),
+                Just(String::from("move 1 to 1 from 1")),
+                Just(String::from("move -1 from 1 to 1")),
+                Just(String::from("move 1 from 1 to 99")),
+                Just(String::from("move 1000 from 1 to 1")),
+                "[a-z ]{1,10}",
+            ],
+        ) {
+            let text = format!("{}{}\n", render(&stack, &moves), junk);
+            let e = parse_input(&text).unwrap_err();
+            prop_assert_eq!(e.line, text.lines().count());
+        }
+
+        #[test]
+        fn test_no_panic(input in "(\\[[A-Z]\\]| |[0-9]|move|from|to|\n){0,60}") {
+            let _ = parse_input(&input);
+        }
+    }
+}
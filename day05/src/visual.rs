@@ -0,0 +1,103 @@
+// Watch the crane at work: each step performs one move, and the frame draws the stacks as in the
+// puzzle input, with the move that was just made.
+
+use crate::{move_9000, move_9001, Day05, Move, Stack};
+use aoc_common::visual::Animation;
+use aoc_common::Animate;
+
+struct Crane {
+    stack: Stack,
+    moves: Vec<Move>,
+    // The number of moves made so far.
+    done: usize,
+    apply: fn(&mut Stack, &Move),
+}
+
+impl Animation for Crane {
+    fn frame(&self) -> String {
+        let mut frame = String::new();
+        let top = self.stack.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..top).rev() {
+            let row: Vec<String> = self
+                .stack
+                .iter()
+                .map(|s| match s.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => String::from("   "),
+                })
+                .collect();
+            frame.push_str(row.join(" ").trim_end());
+            frame.push('\n');
+        }
+        let numbers: Vec<String> = (1..=self.stack.len())
+            .map(|n| format!("{:^3}", n))
+            .collect();
+        frame.push_str(&numbers.join(" "));
+        frame.push_str("\n\n");
+        match self.done.checked_sub(1).map(|i| &self.moves[i]) {
+            Some(m) => frame.push_str(&format!(
+                "move {} of {}: {} from {} to {}",
+                self.done,
+                self.moves.len(),
+                m.qty,
+                m.src + 1,
+                m.dst + 1
+            )),
+            None => frame.push_str(&format!("{} moves to make", self.moves.len())),
+        }
+        frame
+    }
+
+    fn step(&mut self) -> bool {
+        match self.moves.get(self.done) {
+            Some(m) => {
+                (self.apply)(&mut self.stack, m);
+                self.done += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn steps(&self) -> usize {
+        self.done
+    }
+}
+
+impl Animate for Day05 {
+    // Part 1 uses the CrateMover 9000, and part 2 the CrateMover 9001.
+    fn animation((stack, moves): &Self::Input, part: u32) -> Box<dyn Animation> {
+        Box::new(Crane {
+            stack: stack.clone(),
+            moves: moves.clone(),
+            done: 0,
+            apply: if part == 1 { move_9000 } else { move_9001 },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_animation() {
+        let input = Day05::parse(include_str!("../data/test.txt")).unwrap();
+        let mut crane = Day05::animation(&input, 1);
+        assert_eq!(
+            crane.frame(),
+            "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3 \n\n4 moves to make"
+        );
+        assert!(crane.step());
+        assert_eq!(
+            crane.frame(),
+            "[D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3 \n\nmove 1 of 4: 1 from 2 to 1"
+        );
+        while crane.step() {}
+        assert_eq!(crane.steps(), 4);
+        assert!(crane
+            .frame()
+            .starts_with("        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n"));
+    }
+}
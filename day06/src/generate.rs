@@ -0,0 +1,39 @@
+// Synthetic inputs: a stream of `size` characters (at least 32).
+//
+// The bulk of the stream is drawn from only eight letters, so start-of-packet markers are common
+// but a start-of-message marker can't occur until the fourteen distinct letters near the end.
+
+use crate::Day06;
+use aoc_common::{Generate, Rng};
+
+impl Generate for Day06 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(32);
+        let mut letters: Vec<char> = ('a'..='z').collect();
+        rng.shuffle(&mut letters);
+
+        let mut stream: String = (0..size - 16).map(|_| letters[rng.below(8)]).collect();
+        let mut message = letters.clone();
+        rng.shuffle(&mut message);
+        stream.extend(&message[..14]);
+        stream.push(letters[0]);
+        stream.push(letters[1]);
+        stream.push('\n');
+        stream
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_generate() {
+        let input = Day06::generate(&mut Rng::new(1), 1000);
+        assert_eq!(input, Day06::generate(&mut Rng::new(1), 1000));
+        let stream = Day06::parse(&input).unwrap();
+        assert!(Day06::part1(&stream) <= Day06::part2(&stream));
+        assert!(Day06::part2(&stream) > 1000 - 32);
+    }
+}
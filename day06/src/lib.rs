@@ -0,0 +1,243 @@
+// Advent of Code 2022 - Day 6
+// Find start-of packet markers in a stream of characters.
+
+mod generate;
+
+use aoc_common::stream::StreamError;
+use aoc_common::{ParseError, Solver, StreamSolver};
+use std::io::BufRead;
+
+// Scans the stream one character at a time, keeping track of the longest run of distinct
+// characters that ends at the current one, so that no window of characters needs to be kept.
+struct Scanner {
+    len: usize,
+    // The number of characters scanned so far.
+    position: usize,
+    // The start of the run of distinct characters.
+    start: usize,
+    // The position after the last occurrence of each letter.
+    seen: [usize; 26],
+    marker: Option<usize>,
+}
+
+impl Scanner {
+    fn new(len: usize) -> Scanner {
+        Scanner {
+            len,
+            position: 0,
+            start: 0,
+            seen: [0; 26],
+            marker: None,
+        }
+    }
+
+    // Scan the next letter (0 to 25 for 'a' to 'z'), returning the marker once it is found.
+    fn push(&mut self, letter: usize) -> Option<usize> {
+        self.start = self.start.max(self.seen[letter]);
+        self.position += 1;
+        self.seen[letter] = self.position;
+        if self.marker.is_none() && self.position - self.start >= self.len {
+            self.marker = Some(self.position);
+        }
+        self.marker
+    }
+}
+
+// The position after the first `len` distinct consecutive letters of `stream`.
+pub fn find_marker(stream: &[char], len: usize) -> Option<usize> {
+    let mut scanner = Scanner::new(len);
+    stream
+        .iter()
+        .find_map(|&c| scanner.push(c as usize - 'a' as usize))
+}
+
+// Report the character at `column` (1-based) of the stream, which isn't a letter.
+fn invalid(column: usize, text: String) -> ParseError {
+    ParseError {
+        file: None,
+        line: 1,
+        column,
+        text,
+        message: String::from("expected a letter (a-z)"),
+    }
+}
+
+// Report that the stream ends, after `len` letters, without a marker of `kind`.
+fn no_marker(len: usize, kind: &str) -> ParseError {
+    ParseError {
+        file: None,
+        line: 1,
+        column: len + 1,
+        text: String::new(),
+        message: format!("expected a {} marker", kind),
+    }
+}
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    const DAY: u32 = 6;
+
+    // The stream is a single line of letters.
+    type Input = Vec<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    // A stream without both markers has no answers, and is rejected.
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let stream = input.strip_suffix('\n').unwrap_or(input);
+        if let Some((i, c)) = stream
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !c.is_ascii_lowercase())
+        {
+            return Err(invalid(i + 1, c.to_string()));
+        }
+        let stream: Vec<char> = stream.chars().collect();
+        for (len, kind) in [(4, "start-of-packet"), (14, "start-of-message")] {
+            if find_marker(&stream, len).is_none() {
+                return Err(no_marker(stream.len(), kind));
+            }
+        }
+        Ok(stream)
+    }
+
+    // Part 1: the position of the first start-of-packet marker.
+    fn part1(stream: &Self::Input) -> usize {
+        find_marker(stream, 4).expect("parse checks for the marker")
+    }
+
+    // Part 2: the position of the first start-of-message marker.
+    fn part2(stream: &Self::Input) -> usize {
+        find_marker(stream, 14).expect("parse checks for the marker")
+    }
+}
+
+impl StreamSolver for Day06 {
+    // The stream is read in blocks, however long the line is.
+    fn solve_stream<R: BufRead>(mut reader: R) -> Result<(usize, usize), StreamError> {
+        let mut packet = Scanner::new(4);
+        let mut message = Scanner::new(14);
+        // The position of a newline, which may only end the stream.
+        let mut newline = None;
+        loop {
+            let block = reader.fill_buf()?;
+            if block.is_empty() {
+                break;
+            }
+            for &b in block {
+                let position = packet.position;
+                if let Some(column) = newline {
+                    return Err(invalid(column, String::from("\n")).into());
+                } else if b == b'\n' {
+                    newline = Some(position + 1);
+                } else if b.is_ascii_lowercase() {
+                    packet.push((b - b'a') as usize);
+                    message.push((b - b'a') as usize);
+                } else {
+                    let text = String::from_utf8_lossy(&[b]).into_owned();
+                    return Err(invalid(position + 1, text).into());
+                }
+            }
+            let len = block.len();
+            reader.consume(len);
+        }
+        let marker = |scanner: &Scanner, kind: &str| {
+            scanner
+                .marker
+                .ok_or_else(|| no_marker(scanner.position, kind))
+        };
+        Ok((
+            marker(&packet, "start-of-packet")?,
+            marker(&message, "start-of-message")?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{report, Generate, Rng};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_find_marker() {
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11),
+        ];
+        for (stream, marker) in examples {
+            let chars: Vec<char> = stream.chars().collect();
+            assert_eq!(find_marker(&chars, 4), Some(marker));
+        }
+        // The marker can be the last four characters.
+        assert_eq!(find_marker(&['a', 'a', 'b', 'c', 'd'], 4), Some(5));
+        assert_eq!(find_marker(&['a', 'b', 'c'], 4), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let e = Day06::parse("abcd\nefgh\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 5, "\n"));
+        let e = Day06::parse("abcD").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 4, "D"));
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = Day06::generate(&mut Rng::new(6), 3000);
+        let stream = Day06::parse(&input).unwrap();
+        assert_eq!(
+            Day06::solve_stream(input.as_bytes()).unwrap(),
+            (Day06::part1(&stream), Day06::part2(&stream))
+        );
+        for input in ["abcd\nefgh\n", "abcD"] {
+            match Day06::solve_stream(input.as_bytes()) {
+                Err(StreamError::Parse(e)) => assert_eq!(Some(e), Day06::parse(input).err()),
+                _ => panic!("{:?} should be rejected", input),
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_marker() {
+        // Both ways of solving reject a stream without a marker in the same way.
+        for (input, column, kind) in [
+            ("", 1, "start-of-packet"),
+            ("abc\n", 4, "start-of-packet"),
+            ("abcdefg\n", 8, "start-of-message"),
+        ] {
+            let e = report::solve::<Day06>(input, &[1, 2]).unwrap_err();
+            assert_eq!((e.line, e.column, e.text.as_str()), (1, column, ""));
+            assert_eq!(e.message, format!("expected a {} marker", kind));
+            match Day06::solve_stream(input.as_bytes()) {
+                Err(StreamError::Parse(stream)) => assert_eq!(stream, e),
+                _ => panic!("{:?} should be rejected", input),
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(stream in "[a-z]{0,100}") {
+            let chars: Vec<char> = stream.chars().collect();
+            match find_marker(&chars, 14) {
+                Some(_) => prop_assert_eq!(Day06::parse(&stream).unwrap(), chars),
+                None => prop_assert!(Day06::parse(&stream).is_err()),
+            }
+        }
+
+        #[test]
+        fn test_find_marker_windows(stream in "[a-f]{0,40}", len in 1..6usize) {
+            // Compare with checking every window.
+            let chars: Vec<char> = stream.chars().collect();
+            let expected = chars.windows(len).position(|window| {
+                window.iter().collect::<std::collections::HashSet<_>>().len() == len
+            });
+            prop_assert_eq!(find_marker(&chars, len), expected.map(|i| i + len));
+        }
+    }
+}
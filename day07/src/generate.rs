@@ -0,0 +1,85 @@
+// Synthetic inputs: a transcript exploring a file system with `size` directories.
+//
+// Each new directory is made either inside the one made just before it, which builds deep chains,
+// or inside any earlier directory, which builds wide ones. File sizes are scaled so that the disk
+// use is between 40000000 and 70000000, which is the range where part 2 has an answer.
+
+use crate::Day07;
+use aoc_common::{Generate, Rng};
+
+struct Dir {
+    files: Vec<u32>,
+    children: Vec<usize>,
+}
+
+// Write the commands that explore `dir` and its subdirectories, starting from inside it.
+fn explore(dirs: &[Dir], dir: usize, transcript: &mut String) {
+    transcript.push_str("$ ls\n");
+    for &child in &dirs[dir].children {
+        transcript.push_str(&format!("dir d{}\n", child));
+    }
+    for (i, size) in dirs[dir].files.iter().enumerate() {
+        transcript.push_str(&format!("{} f{}.dat\n", size, i));
+    }
+    for &child in &dirs[dir].children {
+        transcript.push_str(&format!("$ cd d{}\n", child));
+        explore(dirs, child, transcript);
+        transcript.push_str("$ cd ..\n");
+    }
+}
+
+impl Generate for Day07 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut dirs = vec![Dir {
+            files: Vec::new(),
+            children: Vec::new(),
+        }];
+        for i in 1..size.max(1) {
+            let parent = if rng.chance(0.5) { i - 1 } else { rng.below(i) };
+            dirs[parent].children.push(i);
+            dirs.push(Dir {
+                files: Vec::new(),
+                children: Vec::new(),
+            });
+        }
+
+        // Pick weights for the files, and scale them to the chosen disk use.
+        let mut weights = Vec::new();
+        for (i, dir) in dirs.iter_mut().enumerate() {
+            for _ in 0..rng.below(4) + usize::from(i == 0) {
+                let weight = rng.between(1, 1000) as u64;
+                dir.files.push(0);
+                weights.push(weight);
+            }
+        }
+        let used = rng.between(40000001, 69999999) as u64;
+        let total: u64 = weights.iter().sum();
+        let mut sizes = weights.iter().map(|w| (w * used / total).max(1) as u32);
+        for dir in dirs.iter_mut() {
+            for file in dir.files.iter_mut() {
+                *file = sizes.next().unwrap();
+            }
+        }
+
+        let mut transcript = String::from("$ cd /\n");
+        explore(&dirs, 0, &mut transcript);
+        transcript
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_generate() {
+        let input = Day07::generate(&mut Rng::new(1), 200);
+        assert_eq!(input, Day07::generate(&mut Rng::new(1), 200));
+        let context = Day07::parse(&input).unwrap();
+        let used = context.node_size("/");
+        assert!((40000000..70000000).contains(&used));
+        assert!(Day07::part2(&context) <= used);
+        Day07::part1(&context);
+    }
+}
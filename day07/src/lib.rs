@@ -0,0 +1,414 @@
+// Advent of Code 2022 - Day 7
+// kdirstat
+
+// TODO: The current implementation assumes unique node names. Refactor so that the main data
+// structure is a proper tree.
+
+mod generate;
+
+use aoc_common::error::{next_field, parse_field};
+use aoc_common::{ParseError, Solver};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NodeType {
+    Dir,
+    File,
+}
+
+#[derive(Debug, PartialEq)]
+enum ModeType {
+    Command,
+    Ls,
+}
+
+#[derive(Debug, PartialEq)]
+struct Node {
+    size: u32,
+    node_type: NodeType,
+    // The name of the node itself, without the full path
+    name: String,
+    parent: Option<String>,
+    children: Vec<String>,
+}
+
+impl Node {
+    fn root() -> Node {
+        Node {
+            size: 0,
+            node_type: NodeType::Dir,
+            parent: None,
+            name: String::from("/"),
+            children: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Context {
+    // Nodes are indexed by their full path
+    nodes: HashMap<String, Node>,
+    // The full path of the current node, e.g. /a/d/d.log
+    pwd: String,
+    mode: ModeType,
+}
+
+impl Context {
+    fn new() -> Context {
+        let mut c = Context {
+            nodes: HashMap::new(),
+            pwd: String::from("/"),
+            mode: ModeType::Command,
+        };
+        c.nodes.insert(String::from("/"), Node::root());
+
+        c
+    }
+
+    fn add_node(&mut self, node_type: NodeType, name: &str, size: u32) {
+        let node = Node {
+            size,
+            name: name.to_string(),
+            node_type,
+            parent: Some((*self.pwd).to_string()),
+            children: Vec::new(),
+        };
+        self.nodes.insert(format!("{}/{}", self.pwd, name), node);
+        let parent_node = self.nodes.get_mut(&self.pwd).unwrap();
+        parent_node.children.push(name.to_string());
+    }
+
+    fn parent(&mut self) -> Option<String> {
+        self.nodes.get(&self.pwd).unwrap().parent.clone()
+    }
+
+    // Caculate the total size of a node, by adding its own size to the size of its children and
+    // theirs, recursively.
+    fn node_size(&self, pwd: &str) -> u32 {
+        let node = self.nodes.get(pwd).unwrap();
+        let mut size = node.size;
+        for child in &node.children {
+            size += self.node_size(format!("{}/{}", pwd, child).as_str());
+        }
+        size
+    }
+
+    // Calculate the total size of all directories of at most the given size.
+    pub fn total_size(&self, size: u32) -> u32 {
+        let mut total = 0;
+        for (name, node) in &self.nodes {
+            if node.node_type == NodeType::Dir && self.node_size(name.as_str()) <= size {
+                total += self.node_size(name.as_str());
+            }
+        }
+        total
+    }
+
+    // Find the smallest directory that, if deleted, would free up enough space to reach the
+    // specified threshold. Return the size of that directory.
+    pub fn smallest_dir(&self, capacity: u32, minimum: u32) -> u32 {
+        let free_space = capacity - self.node_size("/");
+        let shortfall = minimum - free_space;
+        let mut smallest = u32::MAX;
+        for (name, node) in &self.nodes {
+            let ns = self.node_size(name.as_str());
+            if node.node_type == NodeType::Dir && ns > shortfall && ns < smallest {
+                smallest = self.node_size(name.as_str());
+            }
+        }
+        smallest
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), ParseError> {
+        let mut w = line.split_whitespace();
+        match next_field(line, &mut w, "a command or directory entry")? {
+            "$" => match next_field(line, &mut w, "a command")? {
+                "cd" => {
+                    // Change directory.
+                    let dir = next_field(line, &mut w, "a directory")?;
+                    match dir {
+                        ".." => {
+                            self.pwd = self
+                                .parent()
+                                .ok_or_else(|| ParseError::new(line, dir, "already at /"))?
+                        }
+                        "/" => self.pwd = String::from("/"),
+                        name => {
+                            let path = format!("{}/{}", self.pwd, name);
+                            match self.nodes.get(&path) {
+                                Some(node) if node.node_type == NodeType::Dir => {}
+                                Some(_) => {
+                                    return Err(ParseError::new(line, name, "not a directory"))
+                                }
+                                None => {
+                                    return Err(ParseError::new(line, name, "no such directory"))
+                                }
+                            }
+                            self.pwd = path;
+                        }
+                    }
+                    self.mode = ModeType::Command;
+                }
+                "ls" => {
+                    // List directory.
+                    self.mode = ModeType::Ls;
+                }
+                unknown => {
+                    return Err(ParseError::new(line, unknown, "unknown command"));
+                }
+            },
+            entry => {
+                if self.mode != ModeType::Ls {
+                    return Err(ParseError::new(line, entry, "expected a command"));
+                }
+                let (node_type, size) = match entry {
+                    "dir" => (NodeType::Dir, 0),
+                    size => (
+                        NodeType::File,
+                        parse_field(line, size, "'dir' or a file size")?,
+                    ),
+                };
+                let name = next_field(line, &mut w, "a name")?;
+                // A directory may be listed again, but its entries can't change.
+                match self.nodes.get(&format!("{}/{}", self.pwd, name)) {
+                    Some(node) if node.node_type == node_type && node.size == size => {}
+                    Some(_) => return Err(ParseError::new(line, name, "conflicting entry")),
+                    None => self.add_node(node_type, name, size),
+                }
+            }
+        }
+        if let Some(word) = w.next() {
+            return Err(ParseError::new(line, word, "expected end of line"));
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Context, ParseError> {
+    let mut context = Context::new();
+
+    for (i, line) in input.lines().enumerate() {
+        context.parse_line(line).map_err(|e| e.at_line(i + 1))?;
+    }
+
+    Ok(context)
+}
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    const DAY: u32 = 7;
+
+    type Input = Context;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    // Part 1: the total size of all directories of at most 100000.
+    fn part1(context: &Self::Input) -> u32 {
+        context.total_size(100000)
+    }
+
+    // Part 2: the size of the smallest directory to delete to free up enough space for the
+    // update.
+    fn part2(context: &Self::Input) -> u32 {
+        context.smallest_dir(70000000, 30000000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const TEST_INPUT: &str = include_str!("../data/test.txt");
+
+    #[test]
+    fn test_parse_line() {
+        let mut context = Context::new();
+        assert_eq!(context.pwd, "/");
+        assert_eq!(context.mode, ModeType::Command);
+        assert_eq!(context.nodes.len(), 1);
+        assert!(context.nodes.contains_key("/"));
+        context.parse_line("$ cd /").unwrap();
+        assert_eq!(context.pwd, "/");
+        context.parse_line("$ ls").unwrap();
+        context.parse_line("dir a").unwrap();
+        assert_eq!(context.nodes["//a"].size, 0);
+        context.parse_line("14848514 b.txt").unwrap();
+        assert_eq!(context.nodes["//b.txt"].size, 14848514);
+        context.parse_line("8504156 c.dat").unwrap();
+        assert_eq!(context.nodes["//c.dat"].size, 8504156);
+        context.parse_line("dir d").unwrap();
+        assert_eq!(context.nodes["//d"].size, 0);
+        context.parse_line("$ cd a").unwrap();
+        assert_eq!(context.pwd, "//a");
+        assert_eq!(context.nodes["//a"].parent, Some("/".to_string()));
+        context.parse_line("$ cd ..").unwrap();
+        assert_eq!(context.pwd, "/");
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let context = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(context.node_size("//a/e"), 584);
+        assert_eq!(context.node_size("//a"), 94853);
+        assert_eq!(context.node_size("//d"), 24933642);
+        assert_eq!(context.node_size("/"), 48381165);
+    }
+
+    #[test]
+    fn test_total_size() {
+        let context = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(context.total_size(100000), 95437);
+    }
+
+    #[test]
+    fn test_smallest_dir() {
+        let context = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(context.smallest_dir(70000000, 30000000), 24933642);
+    }
+
+    #[test]
+    fn test_parse_line_errors() {
+        let mut context = Context::new();
+        let e = context.parse_line("$ cd ..").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (6, ".."));
+        let e = context.parse_line("$ cd a").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (6, "a"));
+        let e = context.parse_line("$ rm a").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (3, "rm"));
+        let e = context.parse_line("dir a").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (1, "dir"));
+        context.parse_line("$ ls").unwrap();
+        let e = context.parse_line("12x b.txt").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (1, "12x"));
+        let e = context.parse_line("123").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (4, ""));
+        context.parse_line("123 b.txt").unwrap();
+        context.parse_line("123 b.txt").unwrap();
+        let e = context.parse_line("dir b.txt").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (5, "b.txt"));
+        let e = context.parse_line("$ cd b.txt").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (6, "b.txt"));
+        let e = context.parse_line("$ ls -l").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (6, "-l"));
+    }
+
+    // A file system as a list of nodes, each with its name, size (zero for directories) and the
+    // index of its parent directory. The root comes first, and parents come before their children.
+    #[derive(Debug)]
+    struct Tree {
+        nodes: Vec<(String, NodeType, u32, usize)>,
+    }
+
+    impl Tree {
+        fn children(&self, dir: usize) -> impl Iterator<Item = usize> + '_ {
+            (1..self.nodes.len()).filter(move |&i| self.nodes[i].3 == dir)
+        }
+
+        // The transcript of a session that lists every directory, depth first.
+        fn render(&self) -> String {
+            let mut text = String::from("$ cd /\n");
+            self.explore(0, &mut text);
+            text
+        }
+
+        fn explore(&self, dir: usize, text: &mut String) {
+            text.push_str("$ ls\n");
+            for child in self.children(dir) {
+                let (name, node_type, size, _) = &self.nodes[child];
+                match node_type {
+                    NodeType::Dir => text.push_str(&format!("dir {}\n", name)),
+                    NodeType::File => text.push_str(&format!("{} {}\n", size, name)),
+                }
+            }
+            for child in self.children(dir) {
+                if self.nodes[child].1 == NodeType::Dir {
+                    text.push_str(&format!("$ cd {}\n", self.nodes[child].0));
+                    self.explore(child, text);
+                    text.push_str("$ cd ..\n");
+                }
+            }
+        }
+
+        // The nodes that parsing the transcript should produce, indexed by their full path.
+        fn expected(&self) -> HashMap<String, Node> {
+            let mut paths = vec![String::from("/")];
+            let mut nodes = HashMap::from([(String::from("/"), Node::root())]);
+            for (name, node_type, size, parent) in &self.nodes[1..] {
+                let parent_path = paths[*parent].clone();
+                let path = format!("{}/{}", parent_path, name);
+                paths.push(path.clone());
+                nodes.insert(
+                    path,
+                    Node {
+                        size: *size,
+                        node_type: *node_type,
+                        name: name.clone(),
+                        parent: Some(parent_path.clone()),
+                        children: Vec::new(),
+                    },
+                );
+            }
+            for (i, path) in paths.iter().enumerate() {
+                let children = self.children(i).map(|c| self.nodes[c].0.clone()).collect();
+                nodes.get_mut(path).unwrap().children = children;
+            }
+            nodes
+        }
+    }
+
+    fn tree() -> impl Strategy<Value = Tree> {
+        let nodes = (any::<prop::sample::Index>(), any::<bool>(), 1..1_000_000u32);
+        prop::collection::vec(nodes, 0..40).prop_map(|raw| {
+            let mut nodes = vec![(String::from("/"), NodeType::Dir, 0, 0)];
+            let mut dirs = vec![0];
+            for (i, (parent, is_dir, size)) in raw.into_iter().enumerate() {
+                let parent = dirs[parent.index(dirs.len())];
+                if is_dir {
+                    dirs.push(nodes.len());
+                    nodes.push((format!("d{}", i), NodeType::Dir, 0, parent));
+                } else {
+                    nodes.push((format!("f{}.dat", i), NodeType::File, size, parent));
+                }
+            }
+            Tree { nodes }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(tree in tree()) {
+            let context = parse_input(&tree.render()).unwrap();
+            prop_assert_eq!(context.nodes, tree.expected());
+        }
+
+        #[test]
+        fn test_invalid_line(
+            tree in tree(),
+            junk in prop_oneof![
+                Just("$ cd .."),
+                Just("$ cd nowhere"),
+                Just("$ rm -rf /"),
+                Just("$ ls\n5 x.dat\n$ cd x.dat"),
+                Just("$ ls\n5 x.dat\n6 x.dat"),
+                Just("$ ls\ndir"),
+                Just("$ cd /\n1 y.dat"),
+            ],
+        ) {
+            // The transcript ends back in the root directory.
+            let text = format!("{}{}\n", tree.render(), junk);
+            let e = parse_input(&text).unwrap_err();
+            prop_assert_eq!(e.line, text.lines().count());
+        }
+
+        #[test]
+        fn test_no_panic(input in "(\\$|cd|ls|dir|\\.\\.|/|a|b|[0-9]+| |\n){0,60}") {
+            let _ = parse_input(&input);
+        }
+    }
+}
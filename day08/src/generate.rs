@@ -0,0 +1,39 @@
+// Synthetic inputs: a square forest of trees, `size` trees on each side.
+//
+// Heights are biased towards the middle of the range, so that there are both long views and
+// trees hidden behind their neighbours.
+
+use crate::Day08;
+use aoc_common::{Generate, Rng};
+
+impl Generate for Day08 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(2);
+        let mut input = String::with_capacity(size * (size + 1));
+        for _ in 0..size {
+            for _ in 0..size {
+                let height = (rng.below(10) + rng.below(10)) / 2;
+                input.push((b'0' + height as u8) as char);
+            }
+            input.push('\n');
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_generate() {
+        let input = Day08::generate(&mut Rng::new(1), 50);
+        assert_eq!(input, Day08::generate(&mut Rng::new(1), 50));
+        let matrix = Day08::parse(&input).unwrap();
+        assert_eq!((matrix.width(), matrix.height()), (50, 50));
+        // Every tree on the edge is visible.
+        assert!(Day08::part1(&matrix) >= 4 * 49);
+        Day08::part2(&matrix);
+    }
+}
@@ -0,0 +1,221 @@
+// Advent of Code 2022 - Day 8
+// Building a treehouse
+
+// The strategy is to scan the matrix in all four directions, keeping track of the maxima in each
+// row and column, for each direction. Each time a new maximum is found, the coordinates are added
+// to the HashSet.
+//
+// An early stop on each scan can be done if a `9` is hit.
+
+mod generate;
+mod stream;
+
+use aoc_common::{Direction, Grid, ParseError, Point, Solver};
+use std::collections::HashSet;
+use take_until::TakeUntilExt;
+
+fn scan_line(matrix: &Grid<u32>, start: Point, dir: Direction) -> HashSet<Point> {
+    // The starting tree is always visible, and is the first maximum.
+    let mut max = matrix[start];
+    let mut maxima = HashSet::new();
+    maxima.insert(start);
+    for p in matrix.ray(start, dir) {
+        let v = matrix[p];
+        if v > max {
+            max = v;
+            maxima.insert(p);
+        }
+        if v == 9 {
+            break;
+        }
+    }
+    maxima
+}
+
+pub fn find_maxima(matrix: &Grid<u32>) -> HashSet<Point> {
+    let cols = matrix.width() as i32;
+    let rows = matrix.height() as i32;
+    let mut maxima = HashSet::new();
+
+    for row in 0..rows {
+        maxima.extend(scan_line(matrix, Point::new(0, row), Direction::Right));
+        maxima.extend(scan_line(
+            matrix,
+            Point::new(cols - 1, row),
+            Direction::Left,
+        ));
+    }
+    for col in 0..cols {
+        maxima.extend(scan_line(matrix, Point::new(col, 0), Direction::Down));
+        maxima.extend(scan_line(matrix, Point::new(col, rows - 1), Direction::Up));
+    }
+
+    maxima
+}
+
+pub fn tree_score(matrix: &Grid<u32>, tree: Point) -> u64 {
+    let height = matrix[tree];
+
+    // The viewing distance in each direction runs up to and including the first tree that is at
+    // least as high. The product can overflow 32 bits in large forests.
+    Direction::ALL
+        .iter()
+        .map(|&dir| {
+            matrix
+                .ray(tree, dir)
+                .take_until(|&p| matrix[p] >= height)
+                .count() as u64
+        })
+        .product()
+}
+
+// Parse the grid of tree heights. All rows need to be the same width.
+pub fn parse_input(input: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse(input, "a tree height", |c| c.to_digit(10))
+}
+
+pub struct Day08;
+
+impl Solver for Day08 {
+    const DAY: u32 = 8;
+
+    type Input = Grid<u32>;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    // Part 1: the number of trees visible from outside the grid.
+    fn part1(matrix: &Self::Input) -> usize {
+        find_maxima(matrix).len()
+    }
+
+    // Part 2: the highest score of any tree. A tree that is hidden from outside the grid can still
+    // have the best view, so every tree is scored.
+    fn part2(matrix: &Self::Input) -> u64 {
+        matrix
+            .points()
+            .map(|tree| tree_score(matrix, tree))
+            .max()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const TEST_INPUT: &str = include_str!("../data/test.txt");
+
+    #[test]
+    fn test_scan_line() {
+        let matrix = parse_input(TEST_INPUT).unwrap();
+        let maxima = scan_line(&matrix, Point::new(0, 0), Direction::Right);
+        assert_eq!(maxima.len(), 2);
+        assert!(maxima.contains(&Point::new(0, 0)));
+        assert!(maxima.contains(&Point::new(3, 0)));
+        let maxima = scan_line(&matrix, Point::new(1, 0), Direction::Down);
+        assert_eq!(maxima.len(), 2);
+        assert!(maxima.contains(&Point::new(1, 0)));
+        assert!(maxima.contains(&Point::new(1, 1)));
+        let maxima = scan_line(&matrix, Point::new(4, 3), Direction::Left);
+        assert_eq!(maxima.len(), 1);
+        assert!(maxima.contains(&Point::new(4, 3)));
+        let maxima = scan_line(&matrix, Point::new(2, 4), Direction::Up);
+        assert_eq!(maxima.len(), 2);
+        assert!(maxima.contains(&Point::new(2, 4)));
+        assert!(maxima.contains(&Point::new(2, 3)));
+    }
+
+    #[test]
+    fn test_find_highest() {
+        let matrix = parse_input(TEST_INPUT).unwrap();
+        let maxima = find_maxima(&matrix);
+        assert!(maxima.contains(&Point::new(1, 1)));
+        assert!(maxima.contains(&Point::new(2, 1)));
+        assert!(!maxima.contains(&Point::new(3, 1)));
+        assert!(maxima.contains(&Point::new(1, 2)));
+        assert!(!maxima.contains(&Point::new(2, 2)));
+        assert!(maxima.contains(&Point::new(3, 2)));
+        assert!(maxima.contains(&Point::new(2, 3)));
+        assert!(!maxima.contains(&Point::new(1, 3)));
+        assert!(!maxima.contains(&Point::new(3, 3)));
+        assert_eq!(maxima.len(), 21);
+    }
+
+    #[test]
+    fn test_tree_score() {
+        let matrix = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(tree_score(&matrix, Point::new(2, 1)), 4);
+        assert_eq!(tree_score(&matrix, Point::new(2, 3)), 8);
+    }
+
+    #[test]
+    fn test_hidden_view() {
+        // The best view is from the tree in the middle, which can't be seen from outside.
+        let matrix = parse_input("99999\n90009\n90509\n90009\n99999\n").unwrap();
+        assert!(!find_maxima(&matrix).contains(&Point::new(2, 2)));
+        assert_eq!(Day08::part2(&matrix), 16);
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let e = parse_input("303\n2x5\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "x"));
+        let e = parse_input("303\n25\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "25"));
+        assert!(parse_input("").is_err());
+    }
+
+    fn heights() -> impl Strategy<Value = Vec<Vec<u32>>> {
+        (1..20usize, 1..20usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(prop::collection::vec(0..10u32, width), height)
+        })
+    }
+
+    fn render(heights: &[Vec<u32>]) -> String {
+        heights
+            .iter()
+            .map(|row| row.iter().map(|h| h.to_string()).collect::<String>() + "\n")
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(heights in heights()) {
+            let matrix = parse_input(&render(&heights)).unwrap();
+            let rows: Vec<Vec<u32>> = matrix.rows().map(<[u32]>::to_vec).collect();
+            prop_assert_eq!(rows, heights);
+        }
+
+        #[test]
+        fn test_invalid_row(
+            heights in heights(),
+            row in any::<prop::sample::Index>(),
+            column in any::<prop::sample::Index>(),
+            junk in prop_oneof![Just(String::new()), Just(String::from("00")), "[a-z .-]"],
+        ) {
+            // Replace a tree with something else, or with two trees, or with nothing. A change of
+            // width is only detected with another row to compare to.
+            prop_assume!(heights.len() > 1);
+            let text = render(&heights);
+            let mut lines: Vec<String> = text.lines().map(String::from).collect();
+            let y = row.index(lines.len());
+            let x = column.index(lines[y].len());
+            // Emptying a last row of one tree just leaves a trailing newline, which is fine.
+            prop_assume!(!(junk.is_empty() && lines[y].len() == 1 && y == lines.len() - 1));
+            lines[y].replace_range(x..x + 1, &junk);
+            let e = parse_input(&lines.join("\n")).unwrap_err();
+            // The width is set by the first row, so if that changes the second one is reported.
+            prop_assert!(e.line == y + 1 || (y == 0 && e.line == 2));
+        }
+
+        #[test]
+        fn test_no_panic(input in "[0-9a\n]{0,100}") {
+            let _ = parse_input(&input);
+        }
+    }
+}
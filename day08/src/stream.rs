@@ -0,0 +1,189 @@
+// Solving the forest one row at a time, for forests too large to hold in memory.
+//
+// Looking along a row, or down a column from the top, only needs what has been read so far: a
+// monotonic stack of the trees that are taller than everything after them gives both the viewing
+// distance and whether a tree can be seen from the edge. Looking up a column from the bottom needs
+// the rows that are yet to come, so each column keeps a stack of the trees that are taller than
+// everything below them so far. Such a tree is popped as soon as a tree at least as tall turns up,
+// which ends its view downwards; the trees still on the stack at the end are visible from below.
+// Heights are at most 9, so none of the column stacks grows beyond ten trees.
+
+use crate::Day08;
+use aoc_common::stream::{self, StreamError};
+use aoc_common::{ParseError, StreamSolver};
+use std::io::BufRead;
+
+// For each tree in `row`, the viewing distance looking back towards the start of the row, and
+// whether the tree can be seen from there.
+fn look_back<'a>(row: impl Iterator<Item = &'a u8>) -> Vec<(u64, bool)> {
+    let mut stack: Vec<(usize, u8)> = Vec::new();
+    row.enumerate()
+        .map(|(x, &height)| {
+            while stack.last().is_some_and(|&(_, h)| h < height) {
+                stack.pop();
+            }
+            let view = match stack.last() {
+                Some(&(j, _)) => ((x - j) as u64, false),
+                None => (x as u64, true),
+            };
+            stack.push((x, height));
+            view
+        })
+        .collect()
+}
+
+// A tree whose view downwards hasn't ended yet.
+struct Pending {
+    height: u8,
+    row: usize,
+    // The product of the viewing distances in the other three directions.
+    score: u64,
+    // Whether the tree has already been counted as visible.
+    visible: bool,
+}
+
+#[derive(Default)]
+struct Column {
+    // The row of the last tree at least as tall as each height, if any.
+    blocked: [Option<usize>; 10],
+    pending: Vec<Pending>,
+}
+
+impl Column {
+    // Add the tree at `row`, whose viewing distances along its row and visibility from either end
+    // of it are known. Returns the number of trees newly known to be visible, and the best score of
+    // the trees whose views downwards have ended.
+    fn push(&mut self, height: u8, row: usize, sideways: u64, seen: bool) -> (usize, u64) {
+        let up = self.blocked[height as usize].map_or(row, |j| row - j) as u64;
+        let from_top = self.blocked[height as usize].is_none();
+        for blocked in &mut self.blocked[..=height as usize] {
+            *blocked = Some(row);
+        }
+
+        let mut best = 0;
+        while self.pending.last().is_some_and(|p| p.height <= height) {
+            let tree = self.pending.pop().unwrap();
+            best = best.max(tree.score * (row - tree.row) as u64);
+        }
+        let visible = seen || from_top;
+        self.pending.push(Pending {
+            height,
+            row,
+            score: sideways * up,
+            visible,
+        });
+        (visible as usize, best)
+    }
+
+    // The trees left at the end, given the number of rows: their views downwards reach the edge,
+    // where they can be seen from. Returns the number of trees newly known to be visible, and the
+    // best of their scores.
+    fn finish(self, rows: usize) -> (usize, u64) {
+        self.pending.iter().fold((0, 0), |(visible, best), tree| {
+            (
+                visible + !tree.visible as usize,
+                best.max(tree.score * (rows - 1 - tree.row) as u64),
+            )
+        })
+    }
+}
+
+impl StreamSolver for Day08 {
+    fn solve_stream<R: BufRead>(reader: R) -> Result<(usize, u64), StreamError> {
+        let mut columns: Vec<Column> = Vec::new();
+        let mut row = Vec::new();
+        let (mut rows, mut visible, mut best) = (0, 0, 0);
+        stream::for_each_line(reader, |_, line| {
+            // Check the row the same way as `Grid::parse`.
+            row.clear();
+            for (j, c) in line.char_indices() {
+                let height = c.to_digit(10).ok_or_else(|| {
+                    ParseError::new(line, &line[j..j + c.len_utf8()], "expected a tree height")
+                })?;
+                row.push(height as u8);
+            }
+            if row.is_empty() {
+                return Err(ParseError::missing(line, "expected a row of cells").into());
+            } else if rows == 0 {
+                columns.resize_with(row.len(), Column::default);
+            } else if row.len() != columns.len() {
+                return Err(ParseError::new(
+                    line,
+                    line,
+                    format!("expected a row of {} cells", columns.len()),
+                )
+                .into());
+            }
+
+            let left = look_back(row.iter());
+            let mut right = look_back(row.iter().rev());
+            right.reverse();
+            for (x, column) in columns.iter_mut().enumerate() {
+                let ((left, from_left), (right, from_right)) = (left[x], right[x]);
+                let (seen, ended) =
+                    column.push(row[x], rows, left * right, from_left || from_right);
+                visible += seen;
+                best = best.max(ended);
+            }
+            rows += 1;
+            Ok(())
+        })?;
+        if rows == 0 {
+            return Err(ParseError::missing("", "expected a grid").at_line(1).into());
+        }
+
+        for column in columns {
+            let (seen, ended) = column.finish(rows);
+            visible += seen;
+            best = best.max(ended);
+        }
+        Ok((visible, best))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+    use aoc_common::{Generate, Rng, Solver};
+    use proptest::prelude::*;
+
+    const TEST_INPUT: &str = include_str!("../data/test.txt");
+
+    #[test]
+    fn test_look_back() {
+        let row = [3, 0, 3, 7, 3];
+        assert_eq!(
+            look_back(row.iter()),
+            vec![(0, true), (1, false), (2, false), (3, true), (1, false)]
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        assert_eq!(Day08::solve_stream(TEST_INPUT.as_bytes()).unwrap(), (21, 8));
+        let input = Day08::generate(&mut Rng::new(8), 60);
+        let matrix = parse_input(&input).unwrap();
+        assert_eq!(
+            Day08::solve_stream(input.as_bytes()).unwrap(),
+            (Day08::part1(&matrix), Day08::part2(&matrix))
+        );
+        for input in ["303\n2x5\n", "303\n25\n", "303\n\n", ""] {
+            match Day08::solve_stream(input.as_bytes()) {
+                Err(StreamError::Parse(e)) => assert_eq!(Some(e), parse_input(input).err()),
+                _ => panic!("{:?} should be rejected", input),
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_same_answers(heights in "([0-9]{7}\n){1,7}") {
+            let matrix = parse_input(&heights).unwrap();
+            prop_assert_eq!(
+                Day08::solve_stream(heights.as_bytes()).unwrap(),
+                (Day08::part1(&matrix), Day08::part2(&matrix))
+            );
+        }
+    }
+}
@@ -0,0 +1,31 @@
+// Synthetic inputs: `size` moves of the head of the rope, each of one to twenty steps.
+
+use crate::Day09;
+use aoc_common::{Direction, Generate, Rng};
+
+impl Generate for Day09 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            let dir = rng.choose(&Direction::ALL);
+            input.push_str(&format!("{} {}\n", dir.letter(), rng.between(1, 20)));
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_generate() {
+        let input = Day09::generate(&mut Rng::new(1), 500);
+        assert_eq!(input, Day09::generate(&mut Rng::new(1), 500));
+        let moves = Day09::parse(&input).unwrap();
+        assert_eq!(moves.len(), 500);
+        // The tail of the long rope moves no further than the tail of the short one.
+        assert!(Day09::part2(&moves) <= Day09::part1(&moves));
+    }
+}
@@ -0,0 +1,279 @@
+// Advent of Code 2022 - Day 9
+// A pet on a leash in NetHack
+
+mod generate;
+mod visual;
+
+use aoc_common::error::{next_field, parse_field};
+use aoc_common::{Direction, ParseError, Point, Solver};
+use std::collections::HashSet;
+
+// Parse a direction letter and the number of steps to take, e.g. "R 4".
+fn parse_move(line: &str) -> Result<(Direction, usize), ParseError> {
+    let mut words = line.split_whitespace();
+    let word = next_field(line, &mut words, "a direction")?;
+    let mut letters = word.chars();
+    let dir = match (letters.next().map(Direction::try_from), letters.next()) {
+        (Some(Ok(dir)), None) => dir,
+        _ => return Err(ParseError::new(line, word, "expected U, D, L or R")),
+    };
+    let n = parse_field(
+        line,
+        next_field(line, &mut words, "a number of steps")?,
+        "a number of steps",
+    )?;
+    if let Some(word) = words.next() {
+        return Err(ParseError::new(line, word, "expected end of line"));
+    }
+    Ok((dir, n))
+}
+
+// For each line, read a direction letter and the number of steps to take.
+pub fn parse_input(input: &str) -> Result<Vec<(Direction, usize)>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| parse_move(l).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+struct State {
+    head: Point,
+    tail: Point,
+    tail_visits: HashSet<Point>,
+}
+
+impl State {
+    fn new() -> Self {
+        let mut s = State {
+            head: Point::ORIGIN,
+            tail: Point::ORIGIN,
+            tail_visits: HashSet::new(),
+        };
+        s.tail_visits.insert(Point::ORIGIN);
+        s
+    }
+    fn pull_tail(&mut self) {
+        // If the tail touches the head, no processing is needed.
+        if self.head.chebyshev(self.tail) <= 1 {
+            return;
+        }
+
+        // Otherwise the tail takes a single step towards the head: along the axis if they share a
+        // row or column, or diagonally if they don't.
+        self.tail += (self.head - self.tail).signum();
+        self.tail_visits.insert(self.tail);
+    }
+
+    fn step(&mut self, dir: Direction) {
+        self.head += dir;
+        self.pull_tail();
+    }
+
+    fn nstep(&mut self, n: usize, dir: Direction) {
+        for _ in 0..n {
+            self.step(dir);
+        }
+    }
+
+    fn process(&mut self, moves: &[(Direction, usize)]) {
+        for &(dir, n) in moves {
+            self.nstep(n, dir);
+        }
+    }
+}
+
+struct NKnots {
+    knots: Vec<State>,
+}
+
+impl NKnots {
+    fn new(n: usize) -> Self {
+        let mut s = NKnots { knots: Vec::new() };
+        for _ in 0..n {
+            s.knots.push(State::new());
+        }
+        s
+    }
+
+    fn step(&mut self, dir: Direction) {
+        self.knots[0].step(dir);
+        for k in 1..self.knots.len() {
+            self.knots[k].head = self.knots[k - 1].tail;
+            self.knots[k].pull_tail();
+        }
+    }
+
+    fn nstep(&mut self, n: usize, dir: Direction) {
+        for _ in 0..n {
+            self.step(dir);
+        }
+    }
+
+    fn process(&mut self, moves: &[(Direction, usize)]) {
+        for &(dir, n) in moves {
+            self.nstep(n, dir);
+        }
+    }
+}
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    const DAY: u32 = 9;
+
+    type Input = Vec<(Direction, usize)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    // Part 1: the number of positions visited by the tail of a two-knot rope.
+    fn part1(moves: &Self::Input) -> usize {
+        let mut state = State::new();
+        state.process(moves);
+        state.tail_visits.len()
+    }
+
+    // Part 2: the number of positions visited by the tail of a ten-knot rope.
+    fn part2(moves: &Self::Input) -> usize {
+        let mut rope = NKnots::new(10);
+        rope.process(moves);
+        rope.knots[8].tail_visits.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_step() {
+        let mut state = State::new();
+        assert_eq!(state.head, Point::new(0, 0));
+        assert_eq!(state.tail, Point::new(0, 0));
+        state.step(Direction::Right);
+        assert_eq!(state.head, Point::new(1, 0));
+        assert_eq!(state.tail, Point::new(0, 0));
+        state.step(Direction::Right);
+        assert_eq!(state.head, Point::new(2, 0));
+        assert_eq!(state.tail, Point::new(1, 0));
+        state.step(Direction::Right);
+        assert_eq!(state.head, Point::new(3, 0));
+        assert_eq!(state.tail, Point::new(2, 0));
+        state.step(Direction::Right);
+        assert_eq!(state.head, Point::new(4, 0));
+        assert_eq!(state.tail, Point::new(3, 0));
+        state.step(Direction::Up);
+        assert_eq!(state.head, Point::new(4, -1));
+        assert_eq!(state.tail, Point::new(3, 0));
+        state.nstep(3, Direction::Up);
+        assert_eq!(state.head, Point::new(4, -4));
+        assert_eq!(state.tail, Point::new(4, -3));
+        state.step(Direction::Left);
+        assert_eq!(state.head, Point::new(3, -4));
+        assert_eq!(state.tail, Point::new(4, -3));
+        state.step(Direction::Left);
+        assert_eq!(state.head, Point::new(2, -4));
+        assert_eq!(state.tail, Point::new(3, -4));
+        state.step(Direction::Left);
+        assert_eq!(state.head, Point::new(1, -4));
+        assert_eq!(state.tail, Point::new(2, -4));
+        state.step(Direction::Down);
+        assert_eq!(state.head, Point::new(1, -3));
+        assert_eq!(state.tail, Point::new(2, -4));
+        state.nstep(4, Direction::Right);
+        assert_eq!(state.head, Point::new(5, -3));
+        assert_eq!(state.tail, Point::new(4, -3));
+        state.step(Direction::Down);
+        assert_eq!(state.head, Point::new(5, -2));
+        assert_eq!(state.tail, Point::new(4, -3));
+        state.nstep(5, Direction::Left);
+        assert_eq!(state.head, Point::new(0, -2));
+        assert_eq!(state.tail, Point::new(1, -2));
+        state.step(Direction::Right);
+        assert_eq!(state.head, Point::new(1, -2));
+        assert_eq!(state.tail, Point::new(1, -2));
+        state.step(Direction::Right);
+        assert_eq!(state.head, Point::new(2, -2));
+        assert_eq!(state.tail, Point::new(1, -2));
+    }
+
+    #[test]
+    fn test_process_1() {
+        let mut state = State::new();
+        state.process(&parse_input(include_str!("../data/test.txt")).unwrap());
+        assert_eq!(state.tail_visits.len(), 13);
+    }
+
+    #[test]
+    fn test_nknots() {
+        let mut rope = NKnots::new(10);
+        assert_eq!(rope.knots.len(), 10);
+        assert_eq!(rope.knots[0].head, Point::new(0, 0));
+        assert_eq!(rope.knots[0].tail, Point::new(0, 0));
+        assert_eq!(rope.knots[9].head, Point::new(0, 0));
+        assert_eq!(rope.knots[9].tail, Point::new(0, 0));
+        rope.step(Direction::Right);
+        assert_eq!(rope.knots[0].head, Point::new(1, 0));
+        assert_eq!(rope.knots[0].tail, Point::new(0, 0));
+        assert_eq!(rope.knots[1].head, Point::new(0, 0));
+    }
+
+    #[test]
+    fn test_process_2() {
+        let mut rope = NKnots::new(10);
+        rope.process(&parse_input(include_str!("../data/test2.txt")).unwrap());
+        assert_eq!(rope.knots[8].tail_visits.len(), 36);
+    }
+
+    #[test]
+    fn test_parse_move() {
+        assert_eq!(parse_move("R 4"), Ok((Direction::Right, 4)));
+        let e = parse_move("X 4").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (1, "X"));
+        let e = parse_move("U -4").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (3, "-4"));
+        let e = parse_move("U").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (2, ""));
+    }
+
+    fn moves() -> impl Strategy<Value = Vec<(Direction, usize)>> {
+        prop::collection::vec(
+            (prop::sample::select(Direction::ALL.to_vec()), 0..1000usize),
+            0..50,
+        )
+    }
+
+    fn render(moves: &[(Direction, usize)]) -> String {
+        moves
+            .iter()
+            .map(|(dir, n)| format!("{} {}\n", dir.letter(), n))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(moves in moves()) {
+            prop_assert_eq!(parse_input(&render(&moves)).unwrap(), moves);
+        }
+
+        #[test]
+        fn test_invalid_move(
+            moves in moves(),
+            junk in prop_oneof![Just("X 1"), Just("R -1"), Just("L"), Just("U 1 2"), Just("DD 3")],
+        ) {
+            let text = format!("{}{}\n", render(&moves), junk);
+            let e = parse_input(&text).unwrap_err();
+            prop_assert_eq!(e.line, moves.len() + 1);
+        }
+
+        #[test]
+        fn test_no_panic(input in "[UDLRX0-9 \n-]{0,100}") {
+            let _ = parse_input(&input);
+        }
+    }
+}
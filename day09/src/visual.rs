@@ -0,0 +1,143 @@
+// Watch the rope move: each step moves the head by one position, and the frame shows the part of
+// the plane around the head, with the knots, the starting point (s) and the positions visited by
+// the tail (#).
+
+use crate::{Day09, NKnots};
+use aoc_common::visual::Animation;
+use aoc_common::{Animate, Direction, Point};
+
+// The size of the view, which is centred on the head.
+const WIDTH: i32 = 61;
+const HEIGHT: i32 = 21;
+
+struct Rope {
+    rope: NKnots,
+    // The number of knots that follow the head.
+    tails: usize,
+    moves: Vec<(Direction, usize)>,
+    // The current move, and the number of steps taken in it.
+    current: usize,
+    taken: usize,
+    steps: usize,
+}
+
+impl Rope {
+    // The knots from the head to the tail.
+    fn knots(&self) -> Vec<Point> {
+        let knots = &self.rope.knots;
+        std::iter::once(knots[0].head)
+            .chain(knots[..self.tails].iter().map(|k| k.tail))
+            .collect()
+    }
+
+    fn label(&self, i: usize) -> char {
+        match i {
+            0 => 'H',
+            _ if self.tails == 1 => 'T',
+            i => char::from_digit(i as u32, 10).unwrap(),
+        }
+    }
+}
+
+impl Animation for Rope {
+    fn frame(&self) -> String {
+        let knots = self.knots();
+        let visits = &self.rope.knots[self.tails - 1].tail_visits;
+        let corner = knots[0] - Point::new(WIDTH / 2, HEIGHT / 2);
+
+        let mut frame = String::new();
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                let p = corner + Point::new(x, y);
+                // The knots nearer the head are drawn on top.
+                let c = match knots.iter().position(|&k| k == p) {
+                    Some(i) => self.label(i),
+                    None if p == Point::ORIGIN => 's',
+                    None if visits.contains(&p) => '#',
+                    None => '.',
+                };
+                frame.push(c);
+            }
+            frame.push('\n');
+        }
+        let status = match self.moves.get(self.current) {
+            Some(&(dir, n)) if self.taken > 0 => format!("{} {} ({}/{})", dir, n, self.taken, n),
+            _ => format!("{} moves", self.moves.len()),
+        };
+        frame.push_str(&format!(
+            "{}, step {}, the tail has visited {} positions",
+            status,
+            self.steps,
+            visits.len()
+        ));
+        frame
+    }
+
+    fn step(&mut self) -> bool {
+        // Skip over the moves that are complete, including any of zero steps.
+        while let Some(&(_, n)) = self.moves.get(self.current) {
+            if self.taken < n {
+                break;
+            }
+            self.current += 1;
+            self.taken = 0;
+        }
+        match self.moves.get(self.current) {
+            Some(&(dir, _)) => {
+                self.rope.step(dir);
+                self.taken += 1;
+                self.steps += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn steps(&self) -> usize {
+        self.steps
+    }
+}
+
+impl Animate for Day09 {
+    // Part 1 has a rope of two knots, and part 2 a rope of ten.
+    fn animation(moves: &Self::Input, part: u32) -> Box<dyn Animation> {
+        let tails = if part == 1 { 1 } else { 9 };
+        Box::new(Rope {
+            rope: NKnots::new(tails),
+            tails,
+            moves: moves.clone(),
+            current: 0,
+            taken: 0,
+            steps: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_animation() {
+        let moves = Day09::parse(include_str!("../data/test.txt")).unwrap();
+        let mut rope = Day09::animation(&moves, 1);
+        assert!(rope
+            .frame()
+            .ends_with("8 moves, step 0, the tail has visited 1 positions"));
+        assert!(rope.step());
+        assert!(rope.step());
+        let frame = rope.frame();
+        let centre = frame.lines().nth(HEIGHT as usize / 2).unwrap();
+        assert_eq!(&centre[28..31], "sTH");
+        assert!(frame.ends_with("R 4 (2/4), step 2, the tail has visited 2 positions"));
+        while rope.step() {}
+        assert_eq!(rope.steps(), 24);
+        assert!(rope.frame().ends_with("visited 13 positions"));
+
+        let moves = Day09::parse(include_str!("../data/test2.txt")).unwrap();
+        let mut rope = Day09::animation(&moves, 2);
+        while rope.step() {}
+        assert!(rope.frame().ends_with("visited 36 positions"));
+    }
+}
@@ -0,0 +1,42 @@
+// Synthetic inputs: a program of `size` instructions (at least 240, enough to draw the whole
+// screen).
+//
+// Each `addx` operand is chosen so that `x` stays on the screen, between 0 and 39.
+
+use crate::Day10;
+use aoc_common::{Generate, Rng};
+
+impl Generate for Day10 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut x = 1;
+        let mut program = String::new();
+        for _ in 0..size.max(240) {
+            if rng.chance(0.3) {
+                program.push_str("noop\n");
+            } else {
+                let target = (x + rng.between(-10, 10)).clamp(0, 39);
+                let n = if target == x { 1 } else { target - x };
+                x += n;
+                program.push_str(&format!("addx {}\n", n));
+            }
+        }
+        program
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_generate() {
+        let input = Day10::generate(&mut Rng::new(1), 300);
+        assert_eq!(input, Day10::generate(&mut Rng::new(1), 300));
+        let cpu = Day10::parse(&input).unwrap();
+        Day10::part1(&cpu);
+        let crt = Day10::part2(&cpu);
+        assert_eq!(crt.lines().count(), 6);
+        assert!(crt.lines().all(|l| l.len() == 40));
+    }
+}
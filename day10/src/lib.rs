@@ -1,9 +1,12 @@
 // Advent of Code 2022 CPU Library
 
+mod generate;
+mod visual;
+
+use aoc_common::error::{next_field, parse_field};
+use aoc_common::{ParseError, Solver};
 use std::collections::VecDeque;
 use std::fmt;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum OpCode {
@@ -27,6 +30,24 @@ fn arity(op: &OpCode) -> usize {
     }
 }
 
+// Parse a single instruction, e.g. "addx -11".
+fn parse_op(line: &str) -> Result<OpCode, ParseError> {
+    let mut params = line.split_whitespace();
+    let op = match next_field(line, &mut params, "an opcode")? {
+        "noop" => OpCode::Noop,
+        "addx" => OpCode::Addx(parse_field(
+            line,
+            next_field(line, &mut params, "an operand")?,
+            "an integer operand",
+        )?),
+        unknown => return Err(ParseError::new(line, unknown, "unknown opcode")),
+    };
+    if let Some(param) = params.next() {
+        return Err(ParseError::new(line, param, "expected end of line"));
+    }
+    Ok(op)
+}
+
 #[derive(PartialEq, Eq, Clone, Copy)]
 struct State {
     x: i32,
@@ -35,7 +56,8 @@ struct State {
     op_dt: usize,
 }
 
-struct Cpu {
+#[derive(Clone)]
+pub struct Cpu {
     program: VecDeque<OpCode>,
     state: State,
 }
@@ -69,20 +91,17 @@ impl Cpu {
         }
     }
 
-    fn load(&mut self, filename: &str) {
-        let reader = BufReader::new(File::open(filename).expect("Could not open file"));
-        let lines = reader.lines();
-
-        for line in lines {
-            let line = line.unwrap();
-            let params = line.split_whitespace().collect::<Vec<&str>>();
-            let op = match params[0] {
-                "noop" => OpCode::Noop,
-                "addx" => OpCode::Addx(params[1].parse::<i32>().unwrap()),
-                _ => panic!("Unknown opcode"),
-            };
+    fn load(&mut self, input: &str) -> Result<(), ParseError> {
+        for (i, line) in input.lines().enumerate() {
+            let op = parse_op(line).map_err(|e| e.at_line(i + 1))?;
             self.program.push_back(op);
         }
+        Ok(())
+    }
+
+    // The CPU halts once the last operation in the program has completed.
+    fn halted(&self) -> bool {
+        self.state.current_op.is_none() && self.program.is_empty()
     }
 
     fn tick(&mut self) -> State {
@@ -102,19 +121,13 @@ impl Cpu {
         self.state.op_dt -= 1;
 
         // The value of the register(s) during the current clock cycle
-        let state = self.state.clone();
+        let state = self.state;
 
         // At the end of the cycle, execute the current operation if `dt` has reached zero. This
         // resets the current operation to `None`.
         if self.state.op_dt == 0 {
-            match &self.state.current_op {
-                Some(op) => match op {
-                    OpCode::Noop => {}
-                    OpCode::Addx(n) => {
-                        self.state.x += n;
-                    }
-                },
-                None => {}
+            if let Some(OpCode::Addx(n)) = self.state.current_op {
+                self.state.x += n;
             }
             self.state.current_op = None;
         }
@@ -123,18 +136,79 @@ impl Cpu {
     }
 }
 
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: u32 = 10;
+
+    // A CPU with the program loaded, ready to run from the first clock cycle.
+    type Input = Cpu;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut cpu = Cpu::new();
+        cpu.load(input)?;
+        Ok(cpu)
+    }
+
+    // Part 1: the sum of the signal strengths (clock cycle times the value of `x`) during the
+    // 20th, 60th, 100th, 140th, 180th and 220th cycles.
+    fn part1(cpu: &Self::Input) -> i32 {
+        let mut cpu = cpu.clone();
+        let mut strength = 0;
+        while !cpu.halted() {
+            let state = cpu.tick();
+            if state.t > 220 {
+                break;
+            }
+            if state.t % 40 == 20 {
+                strength += state.t as i32 * state.x;
+            }
+        }
+        strength
+    }
+
+    // Part 2: render the 40x6 CRT image. During each cycle the CRT draws a single pixel, which is
+    // lit if the three pixel wide sprite centered on `x` overlaps it.
+    fn part2(cpu: &Self::Input) -> String {
+        let mut cpu = cpu.clone();
+        let mut crt = String::new();
+        while !cpu.halted() {
+            let state = cpu.tick();
+            if state.t > 240 {
+                break;
+            }
+            let col = ((state.t - 1) % 40) as i32;
+            if col == 0 && state.t > 1 {
+                crt.push('\n');
+            }
+            crt.push(if (state.x - col).abs() <= 1 { '#' } else { '.' });
+        }
+        crt
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_load() {
         let mut cpu = Cpu::new();
-        cpu.load("data/test.txt");
+        cpu.load(include_str!("../data/test.txt")).unwrap();
         assert_eq!(cpu.program.len(), 146);
         assert_eq!(cpu.program[0], OpCode::Addx(15));
         assert_eq!(cpu.program[1], OpCode::Addx(-11));
         assert_eq!(cpu.program[9], OpCode::Noop);
+
+        let e = Cpu::new().load("noop\naddx\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 5, ""));
+        let e = Cpu::new().load("noop\nmulx 3\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "mulx"));
+        let e = Cpu::new().load("addx 1.5\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 6, "1.5"));
     }
 
     #[test]
@@ -168,4 +242,69 @@ mod tests {
         assert_eq!(state.t, 6);
         assert_eq!(state.x, -1);
     }
+    #[test]
+    fn test_part1() {
+        let cpu = Day10::parse(include_str!("../data/test.txt")).unwrap();
+        assert_eq!(Day10::part1(&cpu), 13140);
+    }
+
+    #[test]
+    fn test_part2() {
+        let cpu = Day10::parse(include_str!("../data/test.txt")).unwrap();
+        assert_eq!(
+            Day10::part2(&cpu),
+            "##..##..##..##..##..##..##..##..##..##..\n\
+             ###...###...###...###...###...###...###.\n\
+             ####....####....####....####....####....\n\
+             #####.....#####.....#####.....#####.....\n\
+             ######......######......######......####\n\
+             #######.......#######.......#######....."
+        );
+    }
+
+    fn program() -> impl Strategy<Value = Vec<OpCode>> {
+        let op = prop_oneof![Just(OpCode::Noop), any::<i32>().prop_map(OpCode::Addx)];
+        prop::collection::vec(op, 0..100)
+    }
+
+    fn render(program: &[OpCode]) -> String {
+        program
+            .iter()
+            .map(|op| match op {
+                OpCode::Noop => String::from("noop\n"),
+                OpCode::Addx(n) => format!("addx {}\n", n),
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(program in program()) {
+            let mut cpu = Cpu::new();
+            cpu.load(&render(&program)).unwrap();
+            prop_assert_eq!(Vec::from(cpu.program), program);
+        }
+
+        #[test]
+        fn test_invalid_op(
+            program in program(),
+            junk in prop_oneof![
+                Just(String::from("addx")),
+                Just(String::from("addx 1 2")),
+                Just(String::from("noop 1")),
+                Just(String::from("addx 99999999999")),
+                "[a-z]{1,6}",
+            ],
+        ) {
+            prop_assume!(junk != "noop");
+            let text = format!("{}{}\n", render(&program), junk);
+            let e = Cpu::new().load(&text).unwrap_err();
+            prop_assert_eq!(e.line, program.len() + 1);
+        }
+
+        #[test]
+        fn test_no_panic(input in "(noop|addx|-|[0-9]| |\n){0,60}") {
+            let _ = Cpu::new().load(&input);
+        }
+    }
 }
@@ -1,3 +1,8 @@
+use day10::Day10;
+
 fn main() {
-    println!("Hello, world!");
+    aoc_common::cli::run::<Day10>(env!("CARGO_MANIFEST_DIR"), |part, answer| match part {
+        1 => format!("Part 1: {}", answer),
+        _ => format!("Part 2:\n{}", answer),
+    });
 }
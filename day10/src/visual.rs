@@ -0,0 +1,103 @@
+// Watch the CPU drive the CRT: each step is one clock cycle. The frame shows the instruction being
+// executed, the sprite's position on the current row, the picture drawn so far, and the sum of the
+// signal strengths so far. Part 1 stops after the 220th cycle, and part 2 once the screen is full.
+
+use crate::{Cpu, Day10, State};
+use aoc_common::visual::Animation;
+use aoc_common::Animate;
+
+const WIDTH: usize = 40;
+const HEIGHT: usize = 6;
+
+struct Screen {
+    cpu: Cpu,
+    // The state of the CPU during the last cycle.
+    last: Option<State>,
+    crt: Vec<char>,
+    strength: i32,
+    cycles: usize,
+}
+
+impl Animation for Screen {
+    fn frame(&self) -> String {
+        let mut frame = match self.last {
+            Some(state) => format!(
+                "cycle {}: x = {}, {}\n",
+                state.t,
+                state.x,
+                state.current_op.map_or(String::new(), |op| op.to_string())
+            ),
+            None => String::from("cycle 0\n"),
+        };
+
+        // The sprite is three pixels wide, centred on `x`.
+        let x = self.last.map_or(1, |state| state.x);
+        let sprite: String = (0..WIDTH as i32)
+            .map(|col| if (x - col).abs() <= 1 { '#' } else { '.' })
+            .collect();
+        frame.push_str(&format!("sprite  {}\n\n", sprite));
+
+        for row in self.crt.chunks(WIDTH) {
+            frame.push_str("        ");
+            frame.extend(row);
+            frame.push('\n');
+        }
+        frame.push_str(&format!("\nsignal strength: {}", self.strength));
+        frame
+    }
+
+    fn step(&mut self) -> bool {
+        if self.cpu.halted() || self.steps() >= self.cycles {
+            return false;
+        }
+        let state = self.cpu.tick();
+        let col = ((state.t - 1) % WIDTH) as i32;
+        self.crt[state.t - 1] = if (state.x - col).abs() <= 1 { '#' } else { '.' };
+        if state.t % 40 == 20 {
+            self.strength += state.t as i32 * state.x;
+        }
+        self.last = Some(state);
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.last.map_or(0, |state| state.t)
+    }
+}
+
+impl Animate for Day10 {
+    fn animation(cpu: &Self::Input, part: u32) -> Box<dyn Animation> {
+        Box::new(Screen {
+            cpu: cpu.clone(),
+            last: None,
+            crt: vec![' '; WIDTH * HEIGHT],
+            strength: 0,
+            cycles: if part == 1 { 220 } else { WIDTH * HEIGHT },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solver;
+
+    #[test]
+    fn test_animation() {
+        let cpu = Day10::parse(include_str!("../data/test.txt")).unwrap();
+        let mut screen = Day10::animation(&cpu, 1);
+        assert!(screen.frame().starts_with("cycle 0\nsprite  ###....."));
+        assert!(screen.step());
+        assert!(screen.step());
+        assert!(screen.frame().starts_with("cycle 2: x = 1, ADDX 15\n"));
+        while screen.step() {}
+        assert_eq!(screen.steps(), 220);
+        assert!(screen.frame().ends_with("signal strength: 13140"));
+
+        let mut screen = Day10::animation(&cpu, 2);
+        while screen.step() {}
+        let frame = screen.frame();
+        let rows: Vec<&str> = frame.lines().skip(3).take(HEIGHT).map(str::trim).collect();
+        assert_eq!(rows.join("\n"), Day10::part2(&cpu));
+    }
+}